@@ -0,0 +1,374 @@
+//! The layer between raw keys/buttons and what the game does with them, so that controls can be
+//! rebound.  Systems read [`ActionState`] instead of `Input<KeyCode>` and `Input<GamepadButton>`.
+
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf};
+
+use bevy::{prelude::*, utils::HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const BINDINGS_FILENAME: &str = "bindings.ron";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Dash,
+    Brake,
+    ShedTail,
+    Pause,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    Confirm,
+    Back,
+    ToggleCamera,
+    ResetCamera,
+    ZoomIn,
+    ZoomOut,
+    ToggleDebug,
+}
+
+impl Action {
+    /// In the order they appear on the controls screen
+    pub const ALL: [Self; 19] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Dash,
+        Self::Brake,
+        Self::ShedTail,
+        Self::Pause,
+        Self::MenuUp,
+        Self::MenuDown,
+        Self::MenuLeft,
+        Self::MenuRight,
+        Self::Confirm,
+        Self::Back,
+        Self::ToggleCamera,
+        Self::ResetCamera,
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::ToggleDebug,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MoveUp => "Move Up",
+            Self::MoveDown => "Move Down",
+            Self::MoveLeft => "Move Left",
+            Self::MoveRight => "Move Right",
+            Self::Dash => "Dash",
+            Self::Brake => "Brake",
+            Self::ShedTail => "Shed Tail",
+            Self::Pause => "Pause",
+            Self::MenuUp => "Menu Up",
+            Self::MenuDown => "Menu Down",
+            Self::MenuLeft => "Menu Left",
+            Self::MenuRight => "Menu Right",
+            Self::Confirm => "Confirm",
+            Self::Back => "Back",
+            Self::ToggleCamera => "Toggle Follow Camera",
+            Self::ResetCamera => "Reset Camera",
+            Self::ZoomIn => "Zoom In",
+            Self::ZoomOut => "Zoom Out",
+            Self::ToggleDebug => "Toggle Debug Output",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyCode),
+    Gamepad(GamepadButtonType),
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key:?}"),
+            Self::Gamepad(button) => write!(f, "Pad {button:?}"),
+        }
+    }
+}
+
+/// What a player steers with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputDevice {
+    // the keyboard and every gamepad, for when there's only one player
+    Any,
+    Keyboard,
+    Gamepad(Gamepad),
+    // the other player in an online match, whose inputs arrive over the network
+    Network,
+}
+
+impl fmt::Display for InputDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "Any"),
+            Self::Keyboard => write!(f, "Keyboard"),
+            Self::Gamepad(gamepad) => write!(f, "Gamepad {}", gamepad.id + 1),
+            Self::Network => write!(f, "Online"),
+        }
+    }
+}
+
+/// The raw input an [`ActionState`] is worked out from
+pub struct RawInput<'a> {
+    pub keys: &'a Input<KeyCode>,
+    pub gamepads: &'a Gamepads,
+    pub buttons: &'a Input<GamepadButton>,
+    pub button_axes: &'a Axis<GamepadButton>,
+}
+
+impl RawInput<'_> {
+    // whether the binding is held, was just pressed, and how far it is pressed on `device`
+    fn read(&self, binding: Binding, device: InputDevice) -> (bool, bool, f32) {
+        match binding {
+            Binding::Key(key) => {
+                if matches!(device, InputDevice::Gamepad(_) | InputDevice::Network) {
+                    return (false, false, 0.);
+                }
+                let pressed = self.keys.pressed(key);
+                (
+                    pressed,
+                    self.keys.just_pressed(key),
+                    if pressed { 1. } else { 0. },
+                )
+            }
+            Binding::Gamepad(button_type) => {
+                let mut state = (false, false, 0f32);
+                for gamepad in self.gamepads.iter() {
+                    let on_device = match device {
+                        InputDevice::Any => true,
+                        InputDevice::Keyboard | InputDevice::Network => false,
+                        InputDevice::Gamepad(device_gamepad) => device_gamepad == gamepad,
+                    };
+                    if !on_device {
+                        continue;
+                    }
+                    let button = GamepadButton {
+                        gamepad,
+                        button_type,
+                    };
+                    let pressed = self.buttons.pressed(button);
+                    let value =
+                        self.button_axes
+                            .get(button)
+                            .unwrap_or(if pressed { 1. } else { 0. });
+                    state.0 |= pressed;
+                    state.1 |= self.buttons.just_pressed(button);
+                    state.2 = state.2.max(value);
+                }
+                state
+            }
+        }
+    }
+}
+
+/// Which keys and gamepad buttons trigger each action.
+///
+/// Actions missing from the file keep their default bindings, so adding an action doesn't need
+/// everyone to rebind from scratch.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InputBindings {
+    bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use GamepadButtonType as Pad;
+        use KeyCode as Key;
+
+        let keys_and_buttons = |keys: &[KeyCode], buttons: &[GamepadButtonType]| {
+            keys.iter()
+                .copied()
+                .map(Binding::Key)
+                .chain(buttons.iter().copied().map(Binding::Gamepad))
+                .collect()
+        };
+        let bindings = BTreeMap::from([
+            (Action::MoveUp, keys_and_buttons(&[Key::W, Key::Up], &[])),
+            (
+                Action::MoveDown,
+                keys_and_buttons(&[Key::S, Key::Down], &[]),
+            ),
+            (
+                Action::MoveLeft,
+                keys_and_buttons(&[Key::A, Key::Left], &[]),
+            ),
+            (
+                Action::MoveRight,
+                keys_and_buttons(&[Key::D, Key::Right], &[]),
+            ),
+            (Action::Dash, keys_and_buttons(&[Key::Space], &[Pad::South])),
+            (
+                Action::Brake,
+                keys_and_buttons(&[Key::ShiftLeft], &[Pad::West]),
+            ),
+            (Action::ShedTail, keys_and_buttons(&[Key::E], &[Pad::North])),
+            (
+                Action::Pause,
+                keys_and_buttons(&[Key::P, Key::Escape], &[Pad::Start]),
+            ),
+            (Action::MenuUp, keys_and_buttons(&[Key::Up], &[Pad::DPadUp])),
+            (
+                Action::MenuDown,
+                keys_and_buttons(&[Key::Down], &[Pad::DPadDown]),
+            ),
+            (
+                Action::MenuLeft,
+                keys_and_buttons(&[Key::Left], &[Pad::DPadLeft]),
+            ),
+            (
+                Action::MenuRight,
+                keys_and_buttons(&[Key::Right], &[Pad::DPadRight]),
+            ),
+            (
+                Action::Confirm,
+                keys_and_buttons(&[Key::Return], &[Pad::South]),
+            ),
+            (Action::Back, keys_and_buttons(&[Key::Escape], &[Pad::East])),
+            (
+                Action::ToggleCamera,
+                keys_and_buttons(&[Key::C], &[Pad::Select]),
+            ),
+            (
+                Action::ResetCamera,
+                keys_and_buttons(&[Key::Home], &[Pad::RightThumb]),
+            ),
+            (
+                Action::ZoomIn,
+                keys_and_buttons(&[Key::Equals], &[Pad::RightTrigger2]),
+            ),
+            (
+                Action::ZoomOut,
+                keys_and_buttons(&[Key::Minus], &[Pad::LeftTrigger2]),
+            ),
+            (Action::ToggleDebug, keys_and_buttons(&[Key::F3], &[])),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl InputBindings {
+    fn path() -> PathBuf {
+        storage::data_dir().join(BINDINGS_FILENAME)
+    }
+
+    /// Reads the bindings file, falling back to the defaults if it's missing or unreadable
+    pub fn load() -> Self {
+        let path = Self::path();
+        let loaded = match fs::read_to_string(&path) {
+            Ok(bindings) => ron::from_str::<Self>(&bindings).unwrap_or_else(|e| {
+                warn!("Using default controls, {} is corrupt: {e}", path.display());
+                Self::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                warn!(
+                    "Using default controls, could not read {}: {e}",
+                    path.display()
+                );
+                Self::default()
+            }
+        };
+        let mut bindings = Self::default();
+        bindings.bindings.extend(loaded.bindings);
+        bindings
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let bindings = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        storage::write_atomic(&Self::path(), bindings.as_bytes())
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Makes `binding` the only key (or only gamepad button) for `action`, leaving bindings for
+    /// the other device alone
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|existing| {
+            !matches!(
+                (existing, &binding),
+                (Binding::Key(_), Binding::Key(_)) | (Binding::Gamepad(_), Binding::Gamepad(_))
+            )
+        });
+        bindings.push(binding);
+    }
+
+    /// Whether `action` is held on one particular device, for when each player has their own
+    pub fn pressed_on(&self, action: Action, device: InputDevice, input: &RawInput) -> bool {
+        self.get(action)
+            .iter()
+            .any(|binding| input.read(*binding, device).0)
+    }
+
+    pub fn just_pressed_on(&self, action: Action, device: InputDevice, input: &RawInput) -> bool {
+        self.get(action)
+            .iter()
+            .any(|binding| input.read(*binding, device).1)
+    }
+}
+
+/// What the player is doing this frame, in terms of actions.  Filled in by `update_action_state`
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    // how far each action is pressed, for analog triggers.  1 for keys and digital buttons
+    values: BTreeMap<Action, f32>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Like `Input::clear_just_pressed`: returns whether it was just pressed, and stops anything
+    /// else from seeing it this frame
+    pub fn clear_just_pressed(&mut self, action: Action) -> bool {
+        self.just_pressed.remove(&action)
+    }
+
+    pub fn value(&self, action: Action) -> f32 {
+        self.values.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Works out this frame's actions from every connected device
+    pub fn update(&mut self, bindings: &InputBindings, input: &RawInput) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.values.clear();
+        for action in Action::ALL {
+            let mut value: f32 = 0.;
+            for binding in bindings.get(action) {
+                let (pressed, just_pressed, binding_value) = input.read(*binding, InputDevice::Any);
+                if pressed {
+                    self.pressed.insert(action);
+                }
+                if just_pressed {
+                    self.just_pressed.insert(action);
+                }
+                value = value.max(binding_value);
+            }
+            if value > 0. {
+                self.values.insert(action, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Plays online versus rounds between two headless games in one process, over 127.0.0.1, and
+//! checks they never disagree.  For testing netplay without a second machine or a window.
+//!
+//! `snake-loopback --rounds 10 --loss 0.2`
+
+use std::process::ExitCode;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use snake::{
+    levels::Level,
+    net::{NetError, NetSession},
+    AppState, Food, RestartEvent, RoundResults, Snake, SnakeControls, SnakeGamePlugin,
+};
+
+// gives up on a round that goes on for longer than this many frames
+const MAX_FRAMES_PER_ROUND: u64 = 60 * 60 * 5;
+
+struct Options {
+    rounds: u32,
+    // fraction of packets each side drops on purpose
+    loss: f32,
+}
+
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Self {
+            rounds: 5,
+            loss: 0.,
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let value = args.next().ok_or(format!("{arg} expects a value"))?;
+            match arg.as_str() {
+                "--rounds" => {
+                    options.rounds = value.parse().map_err(|_| format!("bad --rounds {value}"))?;
+                }
+                "--loss" => {
+                    options.loss = value.parse().map_err(|_| format!("bad --loss {value}"))?;
+                }
+                _ => return Err(format!("unknown argument {arg}")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn headless_game(session: NetSession) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SnakeGamePlugin))
+        .insert_resource(session);
+    let timestep = app.world.resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+    app
+}
+
+// heads for food that won't hurt it, turning away from the walls, so both sides do something worth checking
+fn steer(app: &mut App) {
+    let Some(local_player) = app
+        .world
+        .get_resource::<NetSession>()
+        .map(NetSession::local_player)
+    else {
+        return;
+    };
+    let field_size = app.world.resource::<Level>().field_size;
+    let food = app
+        .world
+        .query::<(&Transform, &Food)>()
+        .iter(&app.world)
+        .find(|(_, food)| !food.kind.harmful())
+        .map(|(food, _)| food.translation.truncate());
+    let mut snakes = app
+        .world
+        .query::<(&Snake, &Transform, &mut SnakeControls)>();
+    for (snake, head, mut controls) in snakes.iter_mut(&mut app.world) {
+        if snake.player != local_player {
+            continue;
+        }
+        let head = head.translation.truncate();
+        let seek = food.map_or(Vec2::ZERO, |food| (food - head).normalize_or_zero());
+        let away_from_walls = -head / (field_size / 2.);
+        controls.direction = seek + away_from_walls.powf(3.) * 2.;
+    }
+}
+
+fn state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+fn results(app: &App) -> Vec<(usize, usize, u64, String)> {
+    app.world
+        .resource::<RoundResults>()
+        .ranked()
+        .into_iter()
+        .map(|result| {
+            let cause = result
+                .cause
+                .map_or("survived".to_string(), |cause| cause.to_string());
+            (result.player, result.score, result.ticks, cause)
+        })
+        .collect()
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("snake-loopback: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let sessions = NetSession::host(0).and_then(|mut host| {
+        let port = host.local_addr().map_err(NetError::Io)?.port();
+        let mut client = NetSession::join(&format!("127.0.0.1:{port}"))?;
+        host.set_packet_loss(options.loss);
+        client.set_packet_loss(options.loss);
+        Ok((host, client))
+    });
+    let (host, client) = match sessions {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            eprintln!("snake-loopback: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut host = headless_game(host);
+    let mut client = headless_game(client);
+
+    let mut failed = false;
+    for round in 1..=options.rounds {
+        if round > 1 {
+            // only the host can start a round, the client follows
+            host.world.send_event(RestartEvent);
+        }
+        let mut frames = 0;
+        // wait for both sides to get into the round, then for both of them to finish it
+        let mut started = false;
+        loop {
+            steer(&mut host);
+            steer(&mut client);
+            host.update();
+            client.update();
+            frames += 1;
+            let host_session = host.world.get_resource::<NetSession>();
+            let client_session = client.world.get_resource::<NetSession>();
+            let (Some(host_session), Some(client_session)) = (host_session, client_session) else {
+                println!("round {round}: the connection was lost");
+                return ExitCode::FAILURE;
+            };
+            if host_session.desynced() || client_session.desynced() {
+                println!("round {round}: DESYNC");
+                failed = true;
+                break;
+            }
+            let in_round = host_session.round() == round && client_session.round() == round;
+            let (host_state, client_state) = (state(&host), state(&client));
+            started |=
+                in_round && host_state == AppState::Playing && client_state == AppState::Playing;
+            if started && host_state == AppState::GameOver && client_state == AppState::GameOver {
+                let (host_results, client_results) = (results(&host), results(&client));
+                if host_results == client_results {
+                    println!("round {round}: {frames} frames, {host_results:?}");
+                } else {
+                    println!("round {round}: RESULTS DIFFER\n  host   {host_results:?}\n  client {client_results:?}");
+                    failed = true;
+                }
+                break;
+            }
+            if frames > MAX_FRAMES_PER_ROUND {
+                println!("round {round}: still going after {frames} frames, giving up");
+                failed = true;
+                break;
+            }
+        }
+        if failed {
+            break;
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        println!("all rounds played in step");
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,399 @@
+//! Plays lots of seeded games with a scripted bot and writes out how each one went, so that
+//! balance changes (food health, hunger growth, coin leak, upgrade prices...) can be compared by
+//! numbers instead of by feel.
+//!
+//! `snake-sim --games 1000 --seed 0 --level 0 --csv results.csv --json results.json`
+
+use std::{fmt::Write as _, fs, num::NonZeroUsize, process::ExitCode, thread};
+
+use bevy::prelude::*;
+
+use snake::{
+    env::{Observation, SnakeEnv},
+    upgrades::UpgradeEffect,
+    Game, SnakeControls, Upgrades, SIMULATION_HZ,
+};
+
+// the bot only splits its snake once the tail is long enough to be in its way
+const SPLIT_MIN_TAIL: usize = 8;
+
+// how close walls and tail nodes have to be before the bot steers away from them
+const WALL_AVOID_DISTANCE: f32 = 150.;
+const TAIL_AVOID_DISTANCE: f32 = 120.;
+// obstacles are further in, so it's usually going faster when it comes across one
+const OBSTACLE_AVOID_DISTANCE: f32 = 250.;
+// the nodes right behind the head can't be run into, so they aren't worth dodging
+const TAIL_AVOID_SKIP: usize = 2;
+
+struct Options {
+    games: usize,
+    seed: u64,
+    max_ticks: u64,
+    jobs: usize,
+    // the id of the level to play, the first one in the file if left out
+    level: Option<usize>,
+    csv: Option<String>,
+    json: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            games: 1000,
+            seed: 0,
+            // ten minutes of play, so a bot that never dies doesn't run forever
+            max_ticks: 10 * 60 * SIMULATION_HZ as u64,
+            jobs: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            level: None,
+            csv: None,
+            json: None,
+        }
+    }
+}
+
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} expects a value"));
+            match arg.as_str() {
+                "--games" => options.games = parse_number(&arg, value()?)?,
+                "--seed" => options.seed = parse_number(&arg, value()?)?,
+                "--max-ticks" => options.max_ticks = parse_number(&arg, value()?)?,
+                "--jobs" => options.jobs = parse_number::<usize>(&arg, value()?)?.max(1),
+                "--level" => options.level = Some(parse_number(&arg, value()?)?),
+                "--csv" => options.csv = Some(value()?),
+                "--json" => options.json = Some(value()?),
+                _ => return Err(format!("unknown argument {arg}")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: String) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} expects a number, got {value}"))
+}
+
+/// How one game went
+struct GameSummary {
+    seed: u64,
+    ticks: u64,
+    score: usize,
+    // every coin picked up, including the ones spent
+    coins_earned: f32,
+    upgrades: Vec<String>,
+    // `None` if the game hit the tick limit
+    cause: Option<String>,
+    level_complete: bool,
+}
+
+impl GameSummary {
+    fn seconds(&self) -> f64 {
+        self.ticks as f64 / SIMULATION_HZ
+    }
+
+    fn cause_text(&self) -> &str {
+        match &self.cause {
+            Some(cause) => cause,
+            None if self.level_complete => "level complete",
+            None => "timed out",
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("snake-sim: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // games are split between threads by index, so the results come out the same whatever
+    // `--jobs` is
+    let summaries: Result<Vec<GameSummary>, String> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..options.jobs)
+            .map(|job| {
+                let options = &options;
+                scope.spawn(move || {
+                    let mut env = SnakeEnv::new();
+                    if let Some(level) = options.level {
+                        if !env.set_level(level) {
+                            return Err(format!("there's no level {level}"));
+                        }
+                    }
+                    let summaries = (job..options.games)
+                        .step_by(options.jobs)
+                        .map(|game| {
+                            let seed = options.seed + game as u64;
+                            (game, play(&mut env, seed, options.max_ticks))
+                        })
+                        .collect::<Vec<_>>();
+                    Ok(summaries)
+                })
+            })
+            .collect();
+        let mut summaries = Vec::new();
+        for worker in workers {
+            summaries.extend(worker.join().expect("simulation thread panicked")?);
+        }
+        summaries.sort_by_key(|(game, _)| *game);
+        Ok(summaries.into_iter().map(|(_, summary)| summary).collect())
+    });
+    let summaries = match summaries {
+        Ok(summaries) => summaries,
+        Err(e) => {
+            eprintln!("snake-sim: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut written = Ok(());
+    if let Some(path) = &options.csv {
+        written = written.and(fs::write(path, to_csv(&summaries)));
+    }
+    if let Some(path) = &options.json {
+        written = written.and(fs::write(path, to_json(&summaries)));
+    }
+    if options.csv.is_none() && options.json.is_none() {
+        print!("{}", to_csv(&summaries));
+    }
+    eprint!("{}", overview(&summaries));
+    if let Err(e) = written {
+        eprintln!("snake-sim: could not write results: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn play(env: &mut SnakeEnv, seed: u64, max_ticks: u64) -> GameSummary {
+    let mut observation = env.reset(seed);
+    let mut coins_earned = 0.;
+    let mut coins = observation.coins;
+    let mut done = false;
+    while !done && observation.ticks < max_ticks {
+        (observation, _, done) = env.step(steer(&observation));
+        coins_earned += observation.coins - coins;
+        if !done {
+            buy_upgrades(env, &observation);
+        }
+        // whatever it just spent doesn't count against what it picks up next
+        coins = env.app().world.resource::<Game>().coins;
+    }
+    let cause = env.death_cause().map(|cause| cause.to_string());
+    let level_complete = env.level_complete();
+    let world = &env.app().world;
+    let upgrades = world.resource::<Upgrades>();
+    GameSummary {
+        seed,
+        ticks: observation.ticks,
+        score: observation.score,
+        coins_earned,
+        upgrades: world
+            .resource::<Game>()
+            .upgrades
+            .iter()
+            .filter_map(|id| upgrades.by_id(*id))
+            .map(|upgrade| upgrade.name().to_string())
+            .collect(),
+        cause,
+        level_complete,
+    }
+}
+
+/// Heads for the nearest food or coin bag, steering away from walls, obstacles, poison and its own
+/// tail
+fn steer(observation: &Observation) -> SnakeControls {
+    let head = observation.head_position;
+    let food = || observation.food.iter().zip(&observation.food_kinds);
+    let seek = food()
+        .filter(|(_, kind)| !kind.harmful())
+        .map(|(food, _)| food)
+        .chain(&observation.coin_bags)
+        .min_by(|a, b| {
+            a.distance_squared(head)
+                .total_cmp(&b.distance_squared(head))
+        })
+        .map_or(Vec2::ZERO, |food| (*food - head).normalize_or_zero());
+
+    let mut avoid = Vec2::ZERO;
+    let mut push_away_from = |obstacle: Vec2, avoid_distance: f32| {
+        let away = head - obstacle;
+        let distance = away.length();
+        if distance < avoid_distance {
+            avoid += away.normalize_or_zero() * (1. - distance / avoid_distance);
+        }
+    };
+    let boundary = observation.field_size / 2.;
+    push_away_from(Vec2::new(boundary.x, head.y), WALL_AVOID_DISTANCE);
+    push_away_from(Vec2::new(-boundary.x, head.y), WALL_AVOID_DISTANCE);
+    push_away_from(Vec2::new(head.x, boundary.y), WALL_AVOID_DISTANCE);
+    push_away_from(Vec2::new(head.x, -boundary.y), WALL_AVOID_DISTANCE);
+    for obstacle in &observation.obstacles {
+        let closest = obstacle.shape.closest_point(obstacle.position, head);
+        push_away_from(closest, OBSTACLE_AVOID_DISTANCE);
+    }
+    for tail_node in observation.tail.iter().skip(TAIL_AVOID_SKIP) {
+        push_away_from(*tail_node, TAIL_AVOID_DISTANCE);
+    }
+    for (food, _) in food().filter(|(_, kind)| kind.harmful()) {
+        push_away_from(*food, TAIL_AVOID_DISTANCE);
+    }
+
+    SnakeControls {
+        direction: seek + avoid * 2.,
+        stick: Vec2::ZERO,
+        ability: None,
+    }
+}
+
+/// Buys the cheapest upgrade it can afford and hasn't maxed out, holding off on splitting until the tail gets long.
+/// Abilities are left alone, it doesn't know how to use them
+fn buy_upgrades(env: &mut SnakeEnv, observation: &Observation) {
+    let upgrades = env.app().world.resource::<Upgrades>();
+    let mut affordable: Vec<_> = upgrades
+        .all()
+        .iter()
+        .filter(|upgrade| upgrade.can_buy(observation.coins))
+        .filter(|upgrade| {
+            !upgrade
+                .effects()
+                .iter()
+                .any(|effect| matches!(effect, UpgradeEffect::UnlockAbility(_)))
+        })
+        .filter(|upgrade| {
+            let splits = upgrade
+                .effects()
+                .iter()
+                .any(|effect| matches!(effect, UpgradeEffect::SplitTail(_)));
+            !splits || observation.tail.len() >= SPLIT_MIN_TAIL
+        })
+        .map(|upgrade| (upgrade.id(), upgrade.price()))
+        .collect();
+    affordable.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((id, _)) = affordable.first() {
+        env.buy_upgrade(*id);
+    }
+}
+
+fn to_csv(summaries: &[GameSummary]) -> String {
+    let mut csv =
+        String::from("seed,ticks,seconds,score,coins_earned,upgrades_bought,upgrades,cause\n");
+    for summary in summaries {
+        let _ = writeln!(
+            csv,
+            "{},{},{:.2},{},{:.2},{},{},{}",
+            summary.seed,
+            summary.ticks,
+            summary.seconds(),
+            summary.score,
+            summary.coins_earned,
+            summary.upgrades.len(),
+            csv_field(&summary.upgrades.join(";")),
+            csv_field(summary.cause_text()),
+        );
+    }
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn to_json(summaries: &[GameSummary]) -> String {
+    let mut json = String::from("[\n");
+    for (index, summary) in summaries.iter().enumerate() {
+        let upgrades: Vec<_> = summary
+            .upgrades
+            .iter()
+            .map(|name| json_string(name))
+            .collect();
+        let _ = write!(
+            json,
+            "  {{\"seed\": {}, \"ticks\": {}, \"seconds\": {:.2}, \"score\": {}, \"coins_earned\": {:.2}, \"upgrades\": [{}], \"cause\": {}, \"level_complete\": {}}}",
+            summary.seed,
+            summary.ticks,
+            summary.seconds(),
+            summary.score,
+            summary.coins_earned,
+            upgrades.join(", "),
+            summary.cause.as_deref().map_or("null".to_string(), json_string),
+            summary.level_complete,
+        );
+        json.push_str(if index + 1 < summaries.len() {
+            ",\n"
+        } else {
+            "\n"
+        });
+    }
+    json.push_str("]\n");
+    json
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// A few averages, so a run can be eyeballed without opening the files
+fn overview(summaries: &[GameSummary]) -> String {
+    let mut overview = String::new();
+    let games = summaries.len().max(1) as f64;
+    let mean = |value: fn(&GameSummary) -> f64| summaries.iter().map(value).sum::<f64>() / games;
+    let _ = writeln!(overview, "{} games", summaries.len());
+    let _ = writeln!(
+        overview,
+        "  survival     {:.1}s",
+        mean(GameSummary::seconds)
+    );
+    let _ = writeln!(
+        overview,
+        "  score        {:.2}",
+        mean(|summary| summary.score as f64)
+    );
+    let _ = writeln!(
+        overview,
+        "  coins earned {:.2}",
+        mean(|summary| summary.coins_earned as f64)
+    );
+    let _ = writeln!(
+        overview,
+        "  upgrades     {:.2}",
+        mean(|summary| summary.upgrades.len() as f64)
+    );
+    let mut causes: Vec<(&str, usize)> = Vec::new();
+    for summary in summaries {
+        match causes
+            .iter_mut()
+            .find(|(cause, _)| *cause == summary.cause_text())
+        {
+            Some((_, count)) => *count += 1,
+            None => causes.push((summary.cause_text(), 1)),
+        }
+    }
+    causes.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    for (cause, count) in causes {
+        let _ = writeln!(overview, "  {cause:<12} {count}");
+    }
+    overview
+}
@@ -1,2 +1,4 @@
 pub const FOOD_RADIUS: f32 = 15.0;
 pub const SNAKE_HEAD_RADIUS: f32 = 30.0;
+// simulation ticks per second
+pub const SIMULATION_HZ: f64 = 60.0;
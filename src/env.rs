@@ -0,0 +1,263 @@
+//! A gym-style environment over the headless simulation, for training and evaluating automated
+//! players.
+//!
+//! Each [`SnakeEnv::step`] runs exactly one simulation tick, no matter how long it takes in real
+//! time, so episodes are as reproducible as replays: the same seed and the same actions always
+//! give the same observations.
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*, time::TimeUpdateStrategy};
+
+use crate::{
+    buy_upgrade,
+    food::FoodKind,
+    levels::{Level, LevelCatalogue, Levels},
+    obstacles::ObstaclePlacement,
+    replay::ReplayRecorder,
+    upgrades::UpgradeCatalogue,
+    AppState, CoinBag, DeathCause, Food, Game, GameRng, HungerRate, Obstacle, RestartEvent, Rival,
+    RoundResults, Snake, SnakeControls, SnakeGamePlugin, SnakeMaxHealth, SnakeTailNode,
+    TailNodeCount, TailOf, Upgrades, Velocity,
+};
+
+/// Reward for each point of score, i.e each piece of food eaten
+pub const FOOD_REWARD: f32 = 1.;
+/// Reward for each coin picked up
+pub const COIN_REWARD: f32 = 0.1;
+
+/// Everything an agent gets to see of the game after a step
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Observation {
+    pub head_position: Vec2,
+    pub head_velocity: Vec2,
+    // from the node nearest the head to the end of the tail
+    pub tail: Vec<Vec2>,
+    pub food: Vec<Vec2>,
+    // what each of `food` is, in the same order
+    pub food_kinds: Vec<FoodKind>,
+    pub coin_bags: Vec<Vec2>,
+    pub obstacles: Vec<ObstaclePlacement>,
+    pub health: f32,
+    pub max_health: f32,
+    pub hunger_rate: f32,
+    pub score: usize,
+    pub coins: f32,
+    pub ticks: u64,
+    // width and height of the arena, which is centred on the origin
+    pub field_size: Vec2,
+}
+
+/// The game with a single snake, steered by whoever calls [`step`](Self::step).
+///
+/// Runs [`SnakeGamePlugin`] under `MinimalPlugins`, so it needs no window, GPU or input devices.
+pub struct SnakeEnv {
+    app: App,
+    score: usize,
+    coins: f32,
+}
+
+impl Default for SnakeEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnakeEnv {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        // the catalogues built into the game, so episodes don't depend on where it's run from
+        app.insert_resource(Upgrades::new(UpgradeCatalogue::built_in()))
+            .insert_resource(Levels::new(LevelCatalogue::built_in()))
+            .add_plugins((MinimalPlugins, SnakeGamePlugin));
+        // one update is one tick, however long the agent took to decide what to do
+        let timestep = app.world.resource::<Time<Fixed>>().timestep();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+        // the very first update doesn't advance time, so get it out of the way here
+        app.update();
+        Self {
+            app,
+            score: 0,
+            coins: 0.,
+        }
+    }
+
+    /// Starts a new episode with the given seed, and returns what it looks like before the first
+    /// step
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.app.insert_resource(GameRng::new(seed));
+        self.app.world.send_event(RestartEvent);
+        // the restart itself happens in `Update`, after this update's ticks have run, so the old
+        // episode doesn't move on any further
+        self.app.update();
+        self.score = 0;
+        self.coins = 0.;
+        self.observe()
+    }
+
+    /// Steers the snake with `action` for one tick.  Returns the new observation, the reward for
+    /// the tick, and whether the episode is over.
+    ///
+    /// Stepping after the episode is over does nothing until the next [`reset`](Self::reset).
+    pub fn step(&mut self, action: SnakeControls) -> (Observation, f32, bool) {
+        if let Some(mut controls) = self.player_controls() {
+            *controls = action;
+        }
+        self.app.update();
+
+        let observation = self.observe();
+        let reward = (observation.score - self.score) as f32 * FOOD_REWARD
+            + (observation.coins - self.coins) * COIN_REWARD;
+        self.score = observation.score;
+        self.coins = observation.coins;
+        (observation, reward, self.done())
+    }
+
+    /// Picks the level the next [`reset`](Self::reset) plays on.  Returns false if there's no level
+    /// with that id
+    pub fn set_level(&mut self, id: usize) -> bool {
+        let mut levels = self.app.world.resource_mut::<Levels>();
+        let exists = levels.by_id(id).is_some();
+        if exists {
+            levels.selected = id;
+        }
+        exists
+    }
+
+    /// Whether the episode is over, i.e the snake has died or the level's goal was reached
+    pub fn done(&self) -> bool {
+        let world = &self.app.world;
+        // the game over screen is only entered on the update after the tick the snake died on
+        *world.resource::<State<AppState>>().get() == AppState::GameOver
+            || world.resource::<NextState<AppState>>().0 == Some(AppState::GameOver)
+    }
+
+    /// Buys an upgrade with the coins collected so far, as if from the upgrades menu.  Returns
+    /// false if it doesn't exist, can't be afforded or the episode is over
+    pub fn buy_upgrade(&mut self, upgrade_id: usize) -> bool {
+        if self.done() {
+            return false;
+        }
+        let bought = self.app.world.run_system_once_with(
+            upgrade_id,
+            |In(upgrade_id): In<usize>,
+             mut commands: Commands,
+             mut game: ResMut<Game>,
+             mut upgrades: ResMut<Upgrades>,
+             mut recorder: ResMut<ReplayRecorder>| {
+                let Some(upgrade) = upgrades.by_id_mut(upgrade_id) else {
+                    return false;
+                };
+                let bought = buy_upgrade(&mut commands, &mut game, upgrade);
+                if bought {
+                    recorder.record_purchase(game.ticks, upgrade_id);
+                }
+                bought
+            },
+        );
+        // spending coins isn't a negative reward
+        self.coins = self.app.world.resource::<Game>().coins;
+        bought
+    }
+
+    /// Whether the episode ended with the level's goal reached
+    pub fn level_complete(&self) -> bool {
+        self.app.world.resource::<RoundResults>().level_complete
+    }
+
+    /// What killed the snake, once the episode is over
+    pub fn death_cause(&self) -> Option<DeathCause> {
+        self.app
+            .world
+            .resource::<RoundResults>()
+            .results
+            .iter()
+            .find(|result| result.player == 0)
+            .and_then(|result| result.cause)
+    }
+
+    /// The app underneath, for anything the environment doesn't expose directly
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    fn player_controls(&mut self) -> Option<Mut<'_, SnakeControls>> {
+        let mut snakes = self
+            .app
+            .world
+            .query_filtered::<(&Snake, &mut SnakeControls), Without<Rival>>();
+        snakes
+            .iter_mut(&mut self.app.world)
+            .find(|(snake, _)| snake.player == 0)
+            .map(|(_, controls)| controls)
+    }
+
+    /// What the game looks like right now, without stepping it
+    pub fn observe(&mut self) -> Observation {
+        let world = &mut self.app.world;
+        let game = world.resource::<Game>();
+        let mut observation = Observation {
+            hunger_rate: world.resource::<HungerRate>().0,
+            score: game.score,
+            coins: game.coins,
+            ticks: game.ticks,
+            field_size: world.resource::<Level>().field_size,
+            ..default()
+        };
+
+        let mut snakes = world.query_filtered::<
+            (Entity, &Snake, &SnakeMaxHealth, &Transform, &Velocity),
+            Without<Rival>,
+        >();
+        let player = snakes
+            .iter(world)
+            .find(|(_, snake, ..)| snake.player == 0)
+            .map(|(entity, snake, max_health, head, Velocity(velocity))| {
+                (
+                    entity,
+                    snake.health,
+                    max_health.0,
+                    head.translation,
+                    *velocity,
+                )
+            });
+        if let Some((snake_entity, health, max_health, head_position, head_velocity)) = player {
+            observation.health = health;
+            observation.max_health = max_health;
+            observation.head_position = head_position.truncate();
+            observation.head_velocity = head_velocity.truncate();
+            let mut tail_nodes = world
+                .query_filtered::<(&Transform, &TailOf, &TailNodeCount), With<SnakeTailNode>>();
+            let mut tail: Vec<_> = tail_nodes
+                .iter(world)
+                .filter(|(_, TailOf(owner), _)| *owner == snake_entity)
+                .map(|(tail_node, _, TailNodeCount(index))| {
+                    (*index, tail_node.translation.truncate())
+                })
+                .collect();
+            tail.sort_by_key(|(index, _)| *index);
+            observation.tail = tail.into_iter().map(|(_, position)| position).collect();
+        }
+
+        let mut food = world.query::<(&Transform, &Food)>();
+        let mut food: Vec<_> = food.iter(world).collect();
+        food.sort_by_key(|(_, food)| food.spawned);
+        (observation.food, observation.food_kinds) = food
+            .into_iter()
+            .map(|(transform, food)| (transform.translation.truncate(), food.kind))
+            .unzip();
+        let mut coin_bags = world.query_filtered::<&Transform, With<CoinBag>>();
+        observation.coin_bags = coin_bags
+            .iter(world)
+            .map(|coins| coins.translation.truncate())
+            .collect();
+        let mut obstacles = world.query::<(&Transform, &Obstacle)>();
+        observation.obstacles = obstacles
+            .iter(world)
+            .map(|(transform, obstacle)| ObstaclePlacement {
+                kind: obstacle.kind,
+                shape: obstacle.shape,
+                position: transform.translation.truncate(),
+            })
+            .collect();
+        observation
+    }
+}
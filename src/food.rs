@@ -0,0 +1,160 @@
+//! The kinds of prey that can turn up on the field, and how often each of them does.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{StatusEffect, FOOD_HEALTH, FOOD_RADIUS};
+
+/// How many pieces of food can be on the field at once
+pub const MAX_FOOD: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FoodKind {
+    #[default]
+    Mouse,
+    // bigger and slower, and more filling, enough to stop being hungry for a while
+    FatRat,
+    // worth coins as well, and a burst of speed
+    GoldenMouse,
+    // hurts instead of feeding, and slows the snake down
+    PoisonedMouse,
+    // rare and quick, and for a few seconds nothing can hurt whoever catches it
+    GlowingMouse,
+    // heavy and slow, and leaves the snake pulling food and coins in for a while
+    IronMouse,
+}
+
+/// Everything that differs between kinds of food
+pub struct FoodStats {
+    pub sprite: &'static str,
+    pub tint: Color,
+    pub radius: f32,
+    // negative for food that hurts
+    pub health: f32,
+    pub score: usize,
+    pub coins: f32,
+    // how fast it runs away.  It wanders about at a fraction of this
+    pub speed: f32,
+    // how far away it can notice a snake from
+    pub sight: f32,
+    // how quickly it notices one, see `update_food_awareness`
+    pub wariness: f32,
+    // how likely it is to be picked, relative to the others
+    pub weight: u32,
+    // what it does to whoever eats it, and for how many seconds
+    pub effect: Option<(StatusEffect, f32)>,
+}
+
+impl FoodKind {
+    pub const ALL: [Self; 6] = [
+        Self::Mouse,
+        Self::FatRat,
+        Self::GoldenMouse,
+        Self::PoisonedMouse,
+        Self::GlowingMouse,
+        Self::IronMouse,
+    ];
+
+    pub fn stats(self) -> FoodStats {
+        match self {
+            Self::Mouse => FoodStats {
+                sprite: "sprites/mouse.png",
+                tint: Color::WHITE,
+                radius: FOOD_RADIUS,
+                health: FOOD_HEALTH,
+                score: 1,
+                coins: 0.,
+                speed: 80.,
+                sight: 250.,
+                wariness: 1.0,
+                weight: 62,
+                effect: None,
+            },
+            Self::FatRat => FoodStats {
+                sprite: "sprites/mouse.png",
+                tint: Color::rgb(0.6, 0.45, 0.35),
+                radius: FOOD_RADIUS * 1.5,
+                health: FOOD_HEALTH * 2.,
+                score: 2,
+                coins: 0.,
+                speed: 50.,
+                sight: 200.,
+                wariness: 0.6,
+                weight: 15,
+                effect: Some((StatusEffect::HungerFree, 8.)),
+            },
+            Self::GoldenMouse => FoodStats {
+                sprite: "sprites/mouse.png",
+                tint: Color::GOLD,
+                radius: FOOD_RADIUS,
+                health: FOOD_HEALTH / 2.,
+                score: 1,
+                coins: 25.,
+                speed: 160.,
+                sight: 300.,
+                wariness: 1.5,
+                weight: 5,
+                effect: Some((StatusEffect::SpeedBoost, 5.)),
+            },
+            Self::PoisonedMouse => FoodStats {
+                sprite: "sprites/mouse.png",
+                tint: Color::rgb(0.4, 0.9, 0.2),
+                radius: FOOD_RADIUS,
+                health: -25.,
+                score: 0,
+                coins: 0.,
+                speed: 80.,
+                sight: 200.,
+                wariness: 0.8,
+                weight: 10,
+                effect: Some((StatusEffect::Slowed, 4.)),
+            },
+            Self::GlowingMouse => FoodStats {
+                sprite: "sprites/mouse.png",
+                tint: Color::rgb(0.7, 0.9, 1.0),
+                radius: FOOD_RADIUS,
+                health: FOOD_HEALTH / 2.,
+                score: 1,
+                coins: 0.,
+                speed: 140.,
+                sight: 300.,
+                wariness: 1.3,
+                weight: 3,
+                effect: Some((StatusEffect::Invulnerable, 5.)),
+            },
+            Self::IronMouse => FoodStats {
+                sprite: "sprites/mouse.png",
+                tint: Color::rgb(0.55, 0.55, 0.6),
+                radius: FOOD_RADIUS,
+                health: FOOD_HEALTH,
+                score: 1,
+                coins: 0.,
+                speed: 60.,
+                sight: 200.,
+                wariness: 0.8,
+                weight: 5,
+                effect: Some((StatusEffect::Magnet, 8.)),
+            },
+        }
+    }
+
+    /// Whether a snake should go out of its way not to eat it
+    pub fn harmful(self) -> bool {
+        self.stats().health < 0.
+    }
+
+    /// Picks what to spawn next, weighted by each kind's `weight`
+    pub fn pick(rng: &mut impl Rng) -> Self {
+        let total: u32 = Self::ALL.iter().map(|kind| kind.stats().weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for kind in Self::ALL {
+            let weight = kind.stats().weight;
+            if roll < weight {
+                return kind;
+            }
+            roll -= weight;
+        }
+        Self::Mouse
+    }
+}
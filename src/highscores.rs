@@ -0,0 +1,245 @@
+//! The table of the best runs, kept in memory and written to the data directory when it changes.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{storage, GameMode};
+
+const HIGH_SCORES_FILENAME: &str = "highscores.ron";
+
+// bump this whenever the format changes in a way old tables can't be read with
+const HIGH_SCORES_VERSION: u32 = 1;
+
+/// How many runs the table keeps
+pub const HIGH_SCORE_TABLE_SIZE: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HighScoreEntry {
+    pub score: usize,
+    // seconds since the unix epoch
+    pub date: u64,
+    pub tail_length: usize,
+    pub seed: u64,
+    pub mode: GameMode,
+    // names of the upgrades bought, in the order they were bought
+    pub upgrades: Vec<String>,
+}
+
+impl HighScoreEntry {
+    /// The date the run ended on, as `YYYY-MM-DD` in UTC
+    pub fn date_string(&self) -> String {
+        // days to civil date, from Howard Hinnant's `civil_from_days`
+        let days = (self.date / 86_400) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HighScoreFile {
+    version: u32,
+    entries: Vec<HighScoreEntry>,
+}
+
+#[derive(Debug)]
+pub enum HighScoreError {
+    Io(io::Error),
+    Format(ron::error::SpannedError),
+    Version(u32),
+}
+
+impl fmt::Display for HighScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read high scores: {e}"),
+            Self::Format(e) => write!(f, "high scores are corrupt: {e}"),
+            Self::Version(version) => write!(
+                f,
+                "high scores are from an incompatible version of the game (v{version}, expected v{HIGH_SCORES_VERSION})"
+            ),
+        }
+    }
+}
+
+/// The best runs so far, best first.
+///
+/// Read from disk once at startup; after that the copy in memory is the source of truth, and
+/// failing to write it back only loses it for the next launch.
+#[derive(Resource)]
+pub struct HighScores {
+    entries: Vec<HighScoreEntry>,
+    path: PathBuf,
+    // where the last recorded run placed, so the game over screen can point it out
+    last_rank: Option<usize>,
+}
+
+impl HighScores {
+    pub fn load() -> Self {
+        let path = storage::data_dir().join(HIGH_SCORES_FILENAME);
+        let entries = match Self::read(&path) {
+            Ok(entries) => entries,
+            Err(HighScoreError::Io(e)) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            // the file may be fine, it's only this launch that goes without it
+            Err(e @ HighScoreError::Io(_)) => {
+                warn!("Starting a fresh high score table: {e}");
+                Vec::new()
+            }
+            Err(e) => {
+                warn!("Starting a fresh high score table: {e}");
+                // keep the old file around rather than overwriting it on the next save
+                let mut backup = path.clone().into_os_string();
+                backup.push(".bak");
+                if let Err(e) = fs::rename(&path, &backup) {
+                    warn!("Could not back up high scores: {e}");
+                }
+                Vec::new()
+            }
+        };
+        Self {
+            entries,
+            path,
+            last_rank: None,
+        }
+    }
+
+    fn read(path: &Path) -> Result<Vec<HighScoreEntry>, HighScoreError> {
+        let file = fs::read_to_string(path).map_err(HighScoreError::Io)?;
+        let file: HighScoreFile = ron::from_str(&file).map_err(HighScoreError::Format)?;
+        if file.version != HIGH_SCORES_VERSION {
+            return Err(HighScoreError::Version(file.version));
+        }
+        let mut entries = file.entries;
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        entries.truncate(HIGH_SCORE_TABLE_SIZE);
+        Ok(entries)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let file = HighScoreFile {
+            version: HIGH_SCORES_VERSION,
+            entries: self.entries.clone(),
+        };
+        let file = ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        storage::write_atomic(&self.path, file.as_bytes())
+    }
+
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    pub fn best(&self) -> usize {
+        self.entries.first().map_or(0, |entry| entry.score)
+    }
+
+    pub fn last_rank(&self) -> Option<usize> {
+        self.last_rank
+    }
+
+    /// Adds a finished run to the table.  Returns where it placed, or `None` if it didn't make it
+    pub fn record(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        // ties go to whoever got there first
+        let rank = self
+            .entries
+            .iter()
+            .position(|existing| entry.score > existing.score)
+            .unwrap_or(self.entries.len());
+        self.last_rank = (rank < HIGH_SCORE_TABLE_SIZE).then_some(rank);
+        if self.last_rank.is_some() {
+            self.entries.insert(rank, entry);
+            self.entries.truncate(HIGH_SCORE_TABLE_SIZE);
+        }
+        self.last_rank
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(scores: &[usize]) -> HighScores {
+        HighScores {
+            entries: scores.iter().map(|&score| entry(score)).collect(),
+            path: PathBuf::new(),
+            last_rank: None,
+        }
+    }
+
+    fn entry(score: usize) -> HighScoreEntry {
+        HighScoreEntry {
+            score,
+            date: 0,
+            tail_length: 0,
+            seed: 0,
+            mode: GameMode::Classic,
+            upgrades: Vec::new(),
+        }
+    }
+
+    fn scores(high_scores: &HighScores) -> Vec<usize> {
+        high_scores
+            .entries()
+            .iter()
+            .map(|entry| entry.score)
+            .collect()
+    }
+
+    #[test]
+    fn records_runs_best_first() {
+        let mut high_scores = table(&[30, 10]);
+        assert_eq!(high_scores.record(entry(20)), Some(1));
+        assert_eq!(high_scores.last_rank(), Some(1));
+        assert_eq!(scores(&high_scores), [30, 20, 10]);
+        assert_eq!(high_scores.best(), 30);
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_run() {
+        let mut high_scores = table(&[30, 20]);
+        assert_eq!(high_scores.record(entry(20)), Some(2));
+    }
+
+    #[test]
+    fn full_table_drops_the_worst_run() {
+        let mut high_scores = table(&[10; HIGH_SCORE_TABLE_SIZE]);
+        assert_eq!(high_scores.record(entry(5)), None);
+        assert_eq!(high_scores.last_rank(), None);
+        assert_eq!(high_scores.record(entry(50)), Some(0));
+        assert_eq!(high_scores.entries().len(), HIGH_SCORE_TABLE_SIZE);
+        assert_eq!(high_scores.best(), 50);
+    }
+
+    #[test]
+    fn formats_dates_in_utc() {
+        let date = |date| HighScoreEntry { date, ..entry(0) }.date_string();
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_709_251_199), "2024-02-29");
+        assert_eq!(date(1_735_689_600), "2025-01-01");
+    }
+}
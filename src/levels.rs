@@ -0,0 +1,338 @@
+//! The arenas runs are played in, declared in `assets/game.levels.ron`: how big the field is, what
+//! it looks like, where obstacles stand, where things turn up and what it takes to win.
+//!
+//! Like the upgrades, the game loads the file as an asset, and again whenever it changes, and a
+//! copy of it is built into the game for headless tools.
+
+use std::{collections::HashSet, error::Error, fmt, io};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    obstacles::{ObstaclePlacement, ObstacleShape},
+    Game, RandNormalized, SIMULATION_HZ, SNAKE_HEAD_RADIUS,
+};
+
+const BUILT_IN_LEVELS: &[u8] = include_bytes!("../assets/game.levels.ron");
+
+/// What it takes to finish a level.  Counts what all the players have done between them
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Goal {
+    /// Goes on until everyone is out
+    #[default]
+    Endless,
+    /// Eat this many pieces of food
+    Score(usize),
+    /// Have this many coins to spend at once
+    Coins(f32),
+    /// Stay alive for this many seconds
+    Survive(f32),
+}
+
+impl Goal {
+    pub fn reached(self, game: &Game) -> bool {
+        match self {
+            Self::Endless => false,
+            Self::Score(score) => game.score >= score,
+            Self::Coins(coins) => game.coins >= coins,
+            Self::Survive(seconds) => game.ticks as f64 >= seconds as f64 * SIMULATION_HZ,
+        }
+    }
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Endless => write!(f, "endless"),
+            Self::Score(score) => write!(f, "eat {score}"),
+            Self::Coins(coins) => write!(f, "save up {coins:.0} coins"),
+            Self::Survive(seconds) => write!(f, "survive {seconds:.0}s"),
+        }
+    }
+}
+
+/// A rectangle things can turn up in
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Zone {
+    pub position: Vec2,
+    pub half_size: Vec2,
+}
+
+/// One arena.  Also the resource for the one being played right now
+#[derive(Resource, Deserialize, Clone, Debug)]
+pub struct Level {
+    // what saves, replays and online matches remember the level by
+    pub id: usize,
+    pub name: String,
+    pub field_size: Vec2,
+    // drawn across the field, from the assets folder
+    #[serde(default = "default_background")]
+    pub background: String,
+    #[serde(default)]
+    pub obstacles: Vec<ObstaclePlacement>,
+    // anywhere on the field if there aren't any
+    #[serde(default)]
+    pub food_zones: Vec<Zone>,
+    #[serde(default)]
+    pub coin_zones: Vec<Zone>,
+    // one per player, in order.  Anyone without one starts somewhere random
+    #[serde(default)]
+    pub snake_starts: Vec<Vec2>,
+    #[serde(default)]
+    pub goal: Goal,
+}
+
+fn default_background() -> String {
+    "background.png".into()
+}
+
+impl Level {
+    /// Somewhere random in one of `zones`, or anywhere at least `edge_margin` in from the edges of
+    /// the field if there are none
+    pub fn random_point(&self, zones: &[Zone], edge_margin: f32, rng: &mut impl Rng) -> Vec2 {
+        let (center, half_size) = match zones {
+            [] => (Vec2::ZERO, self.field_size / 2. - edge_margin),
+            zones => {
+                let zone = zones[rng.gen_range(0..zones.len())];
+                (zone.position, zone.half_size)
+            }
+        };
+        let point = Vec3::random(rng).truncate() - 0.5;
+        center + point * half_size * 2.
+    }
+
+    /// Whether something of `radius` at `point` would be clear of every obstacle
+    pub fn clear_of_obstacles(&self, point: Vec2, radius: f32) -> bool {
+        !self
+            .obstacles
+            .iter()
+            .any(|obstacle| obstacle.shape.overlaps(obstacle.position, point, radius))
+    }
+
+    fn contains(&self, position: Vec2, half_size: Vec2) -> bool {
+        let boundary = self.field_size / 2.;
+        (position - half_size).cmpge(-boundary).all()
+            && (position + half_size).cmple(boundary).all()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let size_is_valid = |size: Vec2| size.is_finite() && size.cmpgt(Vec2::ZERO).all();
+        if self.name.trim().is_empty() {
+            return Err("has no name".into());
+        }
+        if !size_is_valid(self.field_size) {
+            return Err(format!(
+                "has a field size of {}, it has to be bigger than 0",
+                self.field_size
+            ));
+        }
+        for (index, obstacle) in self.obstacles.iter().enumerate() {
+            let size = match obstacle.shape {
+                ObstacleShape::Circle { radius } => Vec2::splat(radius),
+                ObstacleShape::Rect { half_size } => half_size,
+            };
+            if !size_is_valid(size) {
+                return Err(format!(
+                    "has an obstacle ({index}, a {}) with no size",
+                    obstacle.kind
+                ));
+            }
+            if !self.contains(obstacle.position, Vec2::ZERO) {
+                return Err(format!(
+                    "has an obstacle ({index}, a {}) at {}, off the field",
+                    obstacle.kind, obstacle.position
+                ));
+            }
+        }
+        for (zones, what) in [(&self.food_zones, "food"), (&self.coin_zones, "coin")] {
+            for (index, zone) in zones.iter().enumerate() {
+                if !size_is_valid(zone.half_size) {
+                    return Err(format!("has a {what} zone ({index}) with no size"));
+                }
+                if !self.contains(zone.position, zone.half_size) {
+                    return Err(format!(
+                        "has a {what} zone ({index}) reaching off the field"
+                    ));
+                }
+            }
+        }
+        for (player, start) in self.snake_starts.iter().enumerate() {
+            if !self.contains(*start, Vec2::splat(SNAKE_HEAD_RADIUS)) {
+                return Err(format!("starts P{} at {start}, off the field", player + 1));
+            }
+            if !self.clear_of_obstacles(*start, SNAKE_HEAD_RADIUS) {
+                return Err(format!(
+                    "starts P{} at {start}, inside an obstacle",
+                    player + 1
+                ));
+            }
+        }
+        let goal_is_valid = match self.goal {
+            Goal::Endless => true,
+            Goal::Score(score) => score > 0,
+            Goal::Coins(coins) => coins.is_finite() && coins > 0.,
+            Goal::Survive(seconds) => seconds.is_finite() && seconds > 0.,
+        };
+        if !goal_is_valid {
+            return Err(format!(
+                "has a goal ({:?}) that's already reached",
+                self.goal
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Every level, as read from the levels file
+#[derive(Asset, TypePath, Deserialize, Clone)]
+pub struct LevelCatalogue {
+    pub levels: Vec<Level>,
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl Error for LevelError {}
+
+impl LevelCatalogue {
+    /// Where the asset server finds the levels, in the assets folder
+    pub const PATH: &'static str = "game.levels.ron";
+
+    pub fn parse(catalogue: &[u8]) -> Result<Self, LevelError> {
+        let catalogue: Self = ron::de::from_bytes(catalogue).map_err(LevelError::Parse)?;
+        catalogue.validate()?;
+        Ok(catalogue)
+    }
+
+    /// The levels the game was built with
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_LEVELS).expect("the built-in levels are broken")
+    }
+
+    fn validate(&self) -> Result<(), LevelError> {
+        if self.levels.is_empty() {
+            return Err(LevelError::Invalid("there are no levels".into()));
+        }
+        let mut ids = HashSet::new();
+        for level in &self.levels {
+            let invalid = |reason: &str| {
+                LevelError::Invalid(format!("level {} ({}) {reason}", level.id, level.name))
+            };
+            if !ids.insert(level.id) {
+                return Err(invalid("has the same id as another level"));
+            }
+            level.validate().map_err(|reason| invalid(&reason))?;
+        }
+        Ok(())
+    }
+}
+
+/// Every level there is to pick from, in the order they're listed
+#[derive(Resource)]
+pub struct Levels {
+    levels: Vec<Level>,
+    // the id of the level the next run is played on, from the settings
+    pub selected: usize,
+}
+
+impl Levels {
+    pub fn new(catalogue: LevelCatalogue) -> Self {
+        let selected = catalogue.levels[0].id;
+        Self {
+            levels: catalogue.levels,
+            selected,
+        }
+    }
+
+    /// Swaps in a changed levels file.  The selected level stays selected, if it's still there
+    pub fn replace(&mut self, catalogue: LevelCatalogue) {
+        self.levels = catalogue.levels;
+    }
+
+    pub fn by_id(&self, id: usize) -> Option<&Level> {
+        self.levels.iter().find(|level| level.id == id)
+    }
+
+    /// The level with this id, or the first one if there's no such level any more
+    pub fn by_id_or_first(&self, id: usize) -> &Level {
+        self.by_id(id).unwrap_or(&self.levels[0])
+    }
+
+    /// The id of the level `steps` places along the list from this one, wrapping round
+    pub fn step(&self, id: usize, steps: i32) -> usize {
+        let index = self
+            .levels
+            .iter()
+            .position(|level| level.id == id)
+            .unwrap_or(0);
+        let index = (index as i32 + steps).rem_euclid(self.levels.len() as i32);
+        self.levels[index as usize].id
+    }
+}
+
+/// Reads `.levels.ron` files, refusing any with a background that isn't there
+#[derive(Default)]
+pub struct LevelCatalogueLoader;
+
+impl AssetLoader for LevelCatalogueLoader {
+    type Asset = LevelCatalogue;
+    type Settings = ();
+    type Error = LevelError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<LevelCatalogue, LevelError>> {
+        Box::pin(async move {
+            let mut catalogue = Vec::new();
+            reader
+                .read_to_end(&mut catalogue)
+                .await
+                .map_err(LevelError::Io)?;
+            let catalogue = LevelCatalogue::parse(&catalogue)?;
+            for level in &catalogue.levels {
+                if load_context
+                    .read_asset_bytes(level.background.clone())
+                    .await
+                    .is_err()
+                {
+                    return Err(LevelError::Invalid(format!(
+                        "level {} ({}) has a background, {}, that isn't in the assets folder",
+                        level.id, level.name, level.background
+                    )));
+                }
+            }
+            Ok(catalogue)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["levels.ron"]
+    }
+}
+
+/// The levels file, kept loaded so changes to it are picked up while the game runs
+#[derive(Resource)]
+pub struct LevelCatalogueHandle(pub Handle<LevelCatalogue>);
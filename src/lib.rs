@@ -0,0 +1,1677 @@
+// bevy systems routinely trip these, and splitting them up just to appease clippy hurts readability
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use std::{
+    cmp::Ordering,
+    fmt::{self},
+};
+
+use bevy::{
+    input::InputSystem,
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{Material2d, Material2dPlugin},
+    transform::TransformSystem,
+};
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+mod constants;
+pub use constants::{FOOD_RADIUS, SIMULATION_HZ, SNAKE_HEAD_RADIUS};
+
+pub mod actions;
+pub mod env;
+pub mod food;
+pub mod highscores;
+pub mod levels;
+pub mod net;
+pub mod obstacles;
+pub mod replay;
+pub mod savegame;
+pub mod settings;
+pub mod storage;
+pub mod upgrades;
+use actions::{Action, ActionState, InputBindings, InputDevice};
+use food::FoodKind;
+use highscores::HighScores;
+use levels::{Level, LevelCatalogue, LevelCatalogueLoader, Levels};
+use net::NetSession;
+use obstacles::{ObstacleKind, ObstaclePlacement, ObstacleShape};
+use replay::{Replay, ReplayPlayback, ReplayRecorder};
+use settings::{Settings, SettingsEntry};
+use upgrades::{PriceCurve, UpgradeCatalogue, UpgradeCatalogueLoader, UpgradeEffect};
+
+mod systems;
+use systems::{
+    advance_tick, apply_settings, buy_upgrade, capture_net_input, checksum_net_state,
+    close_upgrades_menu, coinbag_leak, collide_with_obstacles, collide_with_tails, consume_items,
+    controls_menu_navigation, debug_output_shown, drag, finish_replay, game_over_menu_navigation,
+    game_over_menu_selected_button_update, game_over_on_quit_clicked, game_over_on_restart_clicked,
+    handle_snake_deaths, interpolate_translation, load_game, load_levels, load_upgrades,
+    lobby_navigation, main_menu_navigation, main_menu_on_continue_clicked,
+    main_menu_on_multiplayer_clicked, main_menu_on_play_clicked, main_menu_on_quit_clicked,
+    move_food, move_snake, move_tail, on_upgrade_clicked, pause_game, pause_menu_event_handler,
+    pause_menu_on_quit_clicked, pause_menu_on_save_clicked, pause_menu_on_settings_clicked,
+    pause_menu_on_upgrades_clicked, pause_menu_selected_button_update, play_back_replay,
+    player_input, pull_items_to_magnets, record_replay_input, reload_levels, reload_upgrades,
+    reset_local_players, restart, restore_physical_translation, save_bindings, save_game,
+    save_replay, save_settings, settings_menu_navigation, setup, shake_upgrade_icons,
+    show_food_alerts, show_game_over, show_purchase_feedback, spawn_ability_output, spawn_coins,
+    spawn_coins_output, spawn_coins_visuals, spawn_controls_menu, spawn_debug_output, spawn_food,
+    spawn_food_visuals, spawn_game_field_quad, spawn_game_over_splash, spawn_level, spawn_lobby,
+    spawn_main_menu, spawn_obstacle_visuals, spawn_pause_menu, spawn_score_output,
+    spawn_settings_menu, spawn_snake, spawn_snake_visuals, spawn_tail_node_visuals,
+    spawn_upgrades_menu, steer_rivals, store_physical_translation, sync_net_inputs,
+    sync_net_session, tick_status_effects, update_ability_output, update_action_state,
+    update_coins_output, update_controls_menu, update_debug_output, update_food_awareness,
+    update_health, update_health_material, update_high_score, update_lobby, update_score_output,
+    update_settings_menu, update_status_effect_icons, update_upgrade_details,
+    update_upgrade_labels, upgrade_menu_event_handler, upgrade_menu_handler,
+    upgrades_menu_navigation, use_abilities,
+};
+
+#[derive(Resource)]
+pub struct HungerRate(f32);
+
+impl Default for HungerRate {
+    fn default() -> Self {
+        Self(5.)
+    }
+}
+
+const FOOD_LAYER: f32 = -1.;
+const OBSTACLE_LAYER: f32 = -0.5;
+
+trait RandNormalized {
+    fn random(rng: &mut impl Rng) -> Self;
+}
+
+impl RandNormalized for Vec3 {
+    fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            x: rng.gen(),
+            y: rng.gen(),
+            z: rng.gen(),
+        }
+    }
+}
+
+/// The one source of randomness for everything that affects gameplay, so that a seed fully
+/// determines food spawns, coin bag values and mouse behaviour.
+///
+/// Systems that use it must have a fixed order relative to each other, or the sequence each of
+/// them sees will change from frame to frame.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    // when set, every run replays the same seed (e.g for daily challenges or bug reproduction)
+    fixed_seed: bool,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            fixed_seed: true,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn from_entropy() -> Self {
+        Self {
+            fixed_seed: false,
+            ..Self::new(rand::random())
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Reseeds for a new run: with the same seed if one was given, otherwise a fresh one
+    pub fn new_run(&mut self) {
+        if !self.fixed_seed {
+            self.seed = rand::random();
+        }
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+    }
+
+    /// Picks up a saved run exactly where it left off
+    pub fn resume(&mut self, seed: u64, rng: ChaCha8Rng) {
+        self.seed = seed;
+        self.rng = rng;
+    }
+
+    pub fn state(&self) -> &ChaCha8Rng {
+        &self.rng
+    }
+
+    pub fn chance(&mut self, chance: f32) -> bool {
+        self.gen::<f32>() < chance
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[derive(Resource)]
+pub struct DebugSettings {
+    output_shown: bool,
+}
+
+/// Which rules a run is being played under, so high scores from different ones aren't mixed up
+#[derive(
+    Resource, Default, Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    // local multiplayer, last snake standing wins
+    Versus,
+    // local multiplayer, the round lasts until everyone is out
+    Coop,
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Classic => write!(f, "Classic"),
+            Self::Versus => write!(f, "Versus"),
+            Self::Coop => write!(f, "Co-op"),
+        }
+    }
+}
+
+/// How well computer-controlled rivals play
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RivalDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl RivalDifficulty {
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    /// How many ticks a rival waits between changing its mind about where to go
+    pub fn reaction_ticks(self) -> u64 {
+        match self {
+            Self::Easy => 24,
+            Self::Normal => 12,
+            Self::Hard => 4,
+        }
+    }
+
+    /// From 0 to 1, how close a rival is willing to cut past walls and bodies, and whether it
+    /// bothers going after coins as well as food
+    pub fn aggression(self) -> f32 {
+        match self {
+            Self::Easy => 0.2,
+            Self::Normal => 0.5,
+            Self::Hard => 0.8,
+        }
+    }
+}
+
+impl fmt::Display for RivalDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Easy => write!(f, "Easy"),
+            Self::Normal => write!(f, "Normal"),
+            Self::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+/// How many computer-controlled snakes join each run, on top of the players
+#[derive(
+    Resource, Default, Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+pub struct Rivals {
+    pub count: usize,
+    pub difficulty: RivalDifficulty,
+}
+
+pub const MAX_RIVALS: usize = 3;
+
+// marks a snake as steered by `steer_rivals` instead of a player
+#[derive(Component)]
+pub struct Rival(pub RivalDifficulty);
+
+const BUTTON_FONT_SIZE: f32 = 30.;
+
+const PRIMARY_FONT_NAME: &str = "fonts/FiraSans-Bold.ttf";
+
+// how many upgrades there are on each row of the upgrades menu
+pub const UPGRADE_GRID_COLUMNS: usize = 3;
+const SECONDARY_FONT_NAME: &str = "fonts/FiraMono-Medium.ttf";
+
+fn get_button() -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            padding: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        border_color: BorderColor(Color::WHITE),
+        background_color: BackgroundColor(Color::NONE),
+        ..default()
+    }
+}
+
+/// The core simulation: the snake, its tail, food, coin bags, hunger, upgrades and the
+/// [`AppState`] machine.
+///
+/// None of this needs a window, renderer or input devices, so it can run headless under
+/// `MinimalPlugins` (for integration tests, or batch simulation on machines without a GPU).
+/// Headless callers steer the snake by writing its [`SnakeControls`], and start a run by setting
+/// `NextState<AppState>` to [`AppState::Playing`].
+///
+/// The simulation runs in `FixedUpdate` at [`SIMULATION_HZ`], so balance doesn't depend on the
+/// frame rate.
+pub struct SnakeGamePlugin;
+
+impl Plugin for SnakeGamePlugin {
+    fn build(&self, app: &mut App) {
+        // the catalogues can be handed in before the plugin is added, otherwise it starts out with
+        // the ones built into the game
+        if !app.world.contains_resource::<Upgrades>() {
+            app.insert_resource(Upgrades::new(UpgradeCatalogue::built_in()));
+        }
+        if !app.world.contains_resource::<Levels>() {
+            app.insert_resource(Levels::new(LevelCatalogue::built_in()));
+        }
+        let levels = app.world.resource::<Levels>();
+        let level = levels.by_id_or_first(levels.selected).clone();
+        app.init_resource::<HungerRate>()
+            .insert_resource(Game::new())
+            .init_resource::<GameMode>()
+            .init_resource::<LocalPlayers>()
+            .init_resource::<Rivals>()
+            .init_resource::<RoundResults>()
+            .insert_resource(GameRng::from_entropy())
+            .insert_resource(ReplayRecorder {
+                replay: Replay::new(0, Rivals::default(), level.id),
+            })
+            .insert_resource(level)
+            .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .add_state::<AppState>()
+            .add_event::<UpgradeIconClickedEvent>()
+            .add_event::<UpgradePurchaseEvent>()
+            .add_event::<GameOverEvent>()
+            .add_event::<SnakeDiedEvent>()
+            .add_event::<RestartEvent>()
+            .add_event::<PauseGameEvent>()
+            .add_event::<UpgradeMenuButtonClickedEvent>()
+            .add_event::<SaveAndQuitEvent>()
+            .add_event::<LoadGameEvent>()
+            .add_systems(Startup, (spawn_snake, spawn_food))
+            .add_systems(
+                FixedUpdate,
+                (
+                    restore_physical_translation,
+                    // in an online match, the tick only goes ahead once both players' inputs are in
+                    sync_net_inputs
+                        .run_if(resource_exists::<NetSession>())
+                        .run_if(simulation_running),
+                    (
+                        play_back_replay.run_if(resource_exists::<ReplayPlayback>()),
+                        // anything bought by the replay has to take effect before this tick
+                        apply_deferred,
+                        record_replay_input,
+                        steer_rivals,
+                        (
+                            use_abilities,
+                            // a shed tail has to be gone before anything collides with it
+                            apply_deferred,
+                            tick_status_effects,
+                        )
+                            .chain(),
+                        (move_snake, collide_with_obstacles).chain(),
+                        update_health,
+                        (update_food_awareness, move_food)
+                            .chain()
+                            .run_if(any_with_component::<Food>()),
+                        pull_items_to_magnets,
+                        collide_with_tails,
+                        drag,
+                        spawn_coins.run_if(not(any_with_component::<CoinBag>())),
+                        coinbag_leak.run_if(any_with_component::<CoinBag>()),
+                        spawn_food,
+                        consume_items.run_if(
+                            any_with_component::<Food>().or_else(any_with_component::<CoinBag>()),
+                        ),
+                        move_tail.run_if(any_with_component::<SnakeTailNode>()),
+                        advance_tick,
+                        handle_snake_deaths,
+                        show_game_over,
+                        checksum_net_state.run_if(resource_exists::<NetSession>()),
+                    )
+                        // chained so that the systems sharing the GameRng always draw from it
+                        // in the same order
+                        .chain()
+                        .run_if(simulation_running)
+                        .run_if(net_tick_ready),
+                    store_physical_translation,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(AppState::GameOver), finish_replay)
+            // Play from the main menu is always a single player run
+            .add_systems(OnEnter(AppState::MainMenu), reset_local_players)
+            .add_systems(
+                PreUpdate,
+                capture_net_input.run_if(resource_exists::<NetSession>()),
+            )
+            .add_systems(
+                Update,
+                (
+                    sync_net_session
+                        .run_if(resource_exists::<NetSession>())
+                        .before(restart),
+                    restart,
+                    load_game.run_if(in_state(AppState::MainMenu)),
+                    spawn_level
+                        .run_if(resource_changed::<Level>())
+                        .after(restart)
+                        .after(load_game),
+                    save_game.run_if(in_state(AppState::Paused)),
+                    on_upgrade_clicked.run_if(in_state(AppState::Upgrades)),
+                    // these always run, no matter what
+                    (pause_game, upgrade_menu_handler),
+                ),
+            );
+    }
+}
+
+/// Everything needed to actually see and play the game: the camera, meshes and sprites for the
+/// simulation's entities, the HUD, the menus and keyboard/gamepad input.
+///
+/// Needs `DefaultPlugins` and [`SnakeGamePlugin`].
+pub struct SnakeRenderPlugin;
+
+impl Plugin for SnakeRenderPlugin {
+    fn build(&self, app: &mut App) {
+        let settings = Settings::load();
+        app.add_plugins((
+            Material2dPlugin::<HealthbarMaterial>::default(),
+            Material2dPlugin::<GameFieldMaterial>::default(),
+            UiMaterialPlugin::<IconHoverEffectMaterial>::default(),
+        ))
+        .insert_resource(CameraSettings {
+            follow_snake: settings.follow_camera,
+        })
+        .insert_resource(settings)
+        .insert_resource(InputBindings::load())
+        .init_resource::<ActionState>()
+        .init_resource::<ControlsMenuState>()
+        .insert_resource(SettingsMenuSelectedEntry(SettingsEntry::ALL[0]))
+        .insert_resource(GameOverMenuSelectedButton::Restart)
+        .insert_resource(PauseMenuSelectedButton::Quit)
+        .insert_resource(MainMenuSelectedButton::Play)
+        .insert_resource(HighScores::load())
+        .insert_resource(DebugSettings {
+            output_shown: false,
+        })
+        .init_asset::<UpgradeCatalogue>()
+        .init_asset_loader::<UpgradeCatalogueLoader>()
+        .init_asset::<LevelCatalogue>()
+        .init_asset_loader::<LevelCatalogueLoader>()
+        .add_event::<PauseMenuButtonHighlightedEvent>()
+        .add_event::<GameOverButtonHighlightedEvent>()
+        .add_systems(
+            Startup,
+            (
+                setup,
+                load_upgrades,
+                load_levels,
+                spawn_score_output,
+                spawn_coins_output,
+                spawn_ability_output,
+                spawn_debug_output,
+            ),
+        )
+        // each screen is spawned when its state is entered, and torn down again when it is left
+        .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+        .add_systems(OnExit(AppState::MainMenu), despawn_screen::<MainMenu>)
+        .add_systems(OnEnter(AppState::Paused), spawn_pause_menu)
+        .add_systems(OnExit(AppState::Paused), despawn_screen::<PauseMenu>)
+        .add_systems(OnEnter(AppState::Settings), spawn_settings_menu)
+        .add_systems(OnEnter(AppState::Controls), spawn_controls_menu)
+        .add_systems(
+            OnExit(AppState::Controls),
+            (despawn_screen::<ControlsMenu>, save_bindings),
+        )
+        .add_systems(
+            OnExit(AppState::Settings),
+            (despawn_screen::<SettingsMenu>, save_settings),
+        )
+        .add_systems(OnEnter(AppState::Lobby), spawn_lobby)
+        .add_systems(OnExit(AppState::Lobby), despawn_screen::<LobbyMenu>)
+        .add_systems(OnEnter(AppState::Upgrades), spawn_upgrades_menu)
+        .add_systems(OnExit(AppState::Upgrades), despawn_screen::<UpgradesMenu>)
+        .add_systems(
+            OnEnter(AppState::GameOver),
+            (
+                spawn_game_over_splash.after(update_high_score),
+                update_high_score,
+                save_replay
+                    .after(finish_replay)
+                    .run_if(not(resource_exists::<ReplayPlayback>()))
+                    // replays only know how to steer one snake
+                    .run_if(resource_equals(GameMode::Classic)),
+            ),
+        )
+        .add_systems(OnExit(AppState::GameOver), despawn_screen::<GameOver>)
+        // sample input before the simulation ticks, so it reacts within the same frame
+        .add_systems(
+            PreUpdate,
+            (
+                update_action_state,
+                player_input.run_if(in_state(AppState::Playing)),
+            )
+                .chain()
+                .after(InputSystem)
+                .before(capture_net_input),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                interpolate_translation,
+                // after the food it follows has been put where it's drawn
+                show_food_alerts.after(interpolate_translation),
+            )
+                .before(TransformSystem::TransformPropagate),
+        )
+        .add_systems(
+            Update,
+            (
+                (
+                    main_menu_navigation,
+                    main_menu_on_continue_clicked,
+                    main_menu_on_play_clicked,
+                    main_menu_on_multiplayer_clicked,
+                    main_menu_on_quit_clicked,
+                )
+                    .run_if(in_state(AppState::MainMenu)),
+                (
+                    game_over_menu_navigation,
+                    game_over_menu_selected_button_update,
+                    game_over_on_restart_clicked,
+                    game_over_on_quit_clicked,
+                )
+                    .run_if(in_state(AppState::GameOver)),
+                (
+                    update_score_output,
+                    update_ability_output,
+                    update_status_effect_icons,
+                    update_health_material,
+                )
+                    .run_if(in_state(AppState::Playing)),
+                (
+                    (upgrade_menu_event_handler, upgrades_menu_navigation).chain(),
+                    close_upgrades_menu,
+                    update_upgrade_labels.run_if(resource_changed::<Upgrades>()),
+                    update_upgrade_details,
+                    show_purchase_feedback,
+                    shake_upgrade_icons,
+                )
+                    .run_if(in_state(AppState::Upgrades)),
+                (
+                    pause_menu_on_upgrades_clicked,
+                    pause_menu_on_settings_clicked,
+                    pause_menu_on_save_clicked,
+                    pause_menu_on_quit_clicked,
+                    pause_menu_selected_button_update,
+                    pause_menu_event_handler,
+                )
+                    .run_if(in_state(AppState::Paused)),
+                (settings_menu_navigation, update_settings_menu)
+                    .chain()
+                    .run_if(in_state(AppState::Settings)),
+                (controls_menu_navigation, update_controls_menu)
+                    .chain()
+                    .run_if(in_state(AppState::Controls)),
+                (lobby_navigation, update_lobby)
+                    .chain()
+                    .run_if(in_state(AppState::Lobby)),
+                // these always run, no matter what
+                (
+                    apply_settings.run_if(resource_changed::<Settings>()),
+                    spawn_snake_visuals,
+                    spawn_tail_node_visuals,
+                    spawn_food_visuals,
+                    spawn_coins_visuals,
+                    spawn_obstacle_visuals,
+                    spawn_game_field_quad.run_if(resource_changed::<Level>()),
+                    update_coins_output,
+                    update_debug_output.run_if(debug_output_shown),
+                    reload_upgrades,
+                    reload_levels,
+                ),
+            ),
+        );
+    }
+}
+
+/// How hard a snake accelerates, raised by the players' speed upgrades
+#[derive(Component, Clone, Copy)]
+pub struct SnakeSpeed {
+    analog: f32,
+    discrete: f32,
+}
+
+impl Default for SnakeSpeed {
+    fn default() -> Self {
+        Self {
+            analog: 500.,
+            discrete: 10.,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Upgrades {
+    upgrades: Vec<Upgrade>,
+    selected_index: usize,
+}
+
+impl Upgrades {
+    pub fn new(catalogue: UpgradeCatalogue) -> Self {
+        Self {
+            upgrades: catalogue.upgrades,
+            selected_index: 0,
+        }
+    }
+
+    /// Swaps in a changed catalogue.  Upgrades already bought keep their effects and levels
+    pub fn replace(&mut self, catalogue: UpgradeCatalogue) {
+        let mut upgrades = catalogue.upgrades;
+        for upgrade in &mut upgrades {
+            upgrade.level = self.by_id(upgrade.id).map_or(0, |old| old.level);
+        }
+        self.upgrades = upgrades;
+        self.selected_index = self
+            .selected_index
+            .min(self.upgrades.len().saturating_sub(1));
+    }
+
+    /// Back to nothing bought, for a new run
+    pub fn reset_levels(&mut self) {
+        for upgrade in &mut self.upgrades {
+            upgrade.level = 0;
+        }
+    }
+
+    /// Levels to match the upgrades bought so far in a continued run
+    pub fn set_levels(&mut self, bought: &[usize]) {
+        for upgrade in &mut self.upgrades {
+            upgrade.level = bought.iter().filter(|id| **id == upgrade.id).count();
+        }
+    }
+
+    fn index_of(&self, id: usize) -> Option<usize> {
+        self.upgrades.iter().position(|upgrade| upgrade.id == id)
+    }
+
+    pub fn by_id(&self, id: usize) -> Option<&Upgrade> {
+        self.upgrades.iter().find(|upgrade| upgrade.id == id)
+    }
+
+    pub fn by_id_mut(&mut self, id: usize) -> Option<&mut Upgrade> {
+        self.upgrades.iter_mut().find(|upgrade| upgrade.id == id)
+    }
+
+    pub fn all(&self) -> &[Upgrade] {
+        &self.upgrades
+    }
+
+    /// Whether an upgrade that unlocks `ability` has been bought this run
+    pub fn unlocked(&self, ability: Ability) -> bool {
+        self.upgrades.iter().any(|upgrade| {
+            upgrade.level > 0
+                && upgrade
+                    .effects
+                    .contains(&UpgradeEffect::UnlockAbility(ability))
+        })
+    }
+}
+
+/// One entry in the upgrade catalogue, see `assets/game.upgrades.ron`
+#[derive(serde::Deserialize, Clone, PartialEq)]
+pub struct Upgrade {
+    id: usize,
+    // what the first level costs
+    price: f32,
+    #[serde(default)]
+    price_curve: PriceCurve,
+    icon: String, // icon path, to be loaded by the asset loader
+    name: String,
+    description: String,
+    // how many times it can be bought in one run, if there's a limit
+    #[serde(default)]
+    max_level: Option<usize>,
+    #[serde(default)]
+    effects: Vec<UpgradeEffect>,
+    // how many times it has been bought this run
+    #[serde(skip)]
+    level: usize,
+}
+
+impl Upgrade {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// What the next level costs
+    pub fn price(&self) -> f32 {
+        self.price_curve.price(self.price, self.level)
+    }
+
+    /// How many times it has been bought this run
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn max_level(&self) -> Option<usize> {
+        self.max_level
+    }
+
+    pub fn maxed_out(&self) -> bool {
+        self.max_level
+            .is_some_and(|max_level| self.level >= max_level)
+    }
+
+    pub fn can_buy(&self, coins: f32) -> bool {
+        !self.maxed_out() && coins > self.price()
+    }
+
+    /// e.g "Lv 2/5", or just "Lv 2" if there's no limit
+    pub fn level_text(&self) -> String {
+        match self.max_level {
+            Some(max_level) => format!("Lv {}/{max_level}", self.level),
+            None => format!("Lv {}", self.level),
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn effects(&self) -> &[UpgradeEffect] {
+        &self.effects
+    }
+
+    /// The next level's price, or that there isn't one
+    pub fn price_text(&self) -> String {
+        if self.maxed_out() {
+            "Max level".into()
+        } else {
+            format!("{0} coins", self.price())
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct UpgradesMenu;
+
+#[derive(Component)]
+pub struct CoinBag {
+    value: f32,
+}
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(States, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    Upgrades,
+    Settings,
+    Controls,
+    Lobby,
+    GameOver,
+}
+
+// despawns every top-level entity of a screen, so it can be torn down when its state is left
+pub fn despawn_screen<T: Component>(
+    mut commands: Commands,
+    screen: Query<Entity, (With<T>, Without<Parent>)>,
+) {
+    for entity in &screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Whether the simulation should advance this tick.  Stops as soon as another state has been
+/// requested, so e.g nothing happens between the snake dying and the game over screen appearing.
+pub fn simulation_running(
+    state: Res<State<AppState>>,
+    next_state: Res<NextState<AppState>>,
+) -> bool {
+    *state.get() == AppState::Playing && next_state.0.is_none()
+}
+
+#[derive(Event, Default)]
+pub struct PauseGameEvent;
+
+/// Whether the other player's input for this tick has arrived, when playing online
+pub fn net_tick_ready(session: Option<Res<NetSession>>) -> bool {
+    session.map_or(true, |session| session.tick_ready())
+}
+
+#[derive(Event, Default)]
+pub struct UpgradeMenuButtonClickedEvent;
+
+#[derive(Event, Default)]
+pub struct SaveAndQuitEvent;
+
+// continues the run left in the saved game
+#[derive(Event, Default)]
+pub struct LoadGameEvent;
+
+#[derive(Event)]
+pub struct GameOverButtonHighlightedEvent(GameOverMenuSelectedButton);
+
+#[derive(Event)]
+pub struct PauseMenuButtonHighlightedEvent(PauseMenuSelectedButton);
+
+// a snake can't reach its own tail until it has this many nodes
+const SELF_COLLISION_MIN_TAIL: usize = 4;
+
+// marker for "Game Over" splash screen and related components
+#[derive(Component)]
+pub struct GameOver;
+
+#[derive(Event, Default)]
+pub struct GameOverEvent;
+
+#[derive(Component)]
+pub struct RestartButton;
+
+#[derive(Component)]
+pub struct QuitButton;
+
+#[derive(Component)]
+pub struct UpgradesButton;
+
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+// which setting a row of the Settings menu shows
+#[derive(Component)]
+pub struct SettingsMenuRow(SettingsEntry);
+
+#[derive(Resource)]
+pub struct SettingsMenuSelectedEntry(SettingsEntry);
+
+#[derive(Component)]
+pub struct ControlsMenu;
+
+/// One line of the Controls menu
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ControlsEntry {
+    Action(Action),
+    ResetDefaults,
+    Back,
+}
+
+impl ControlsEntry {
+    fn all() -> impl Iterator<Item = Self> {
+        Action::ALL
+            .into_iter()
+            .map(Self::Action)
+            .chain([Self::ResetDefaults, Self::Back])
+    }
+}
+
+#[derive(Component)]
+pub struct ControlsMenuRow(ControlsEntry);
+
+#[derive(Resource, Default)]
+pub struct ControlsMenuState {
+    selected: usize,
+    // waiting for the player to press the key or button to bind to the selected action
+    waiting: bool,
+}
+
+#[derive(Component)]
+pub struct PlayButton;
+
+#[derive(Component)]
+pub struct SaveAndQuitButton;
+
+#[derive(Component)]
+pub struct ContinueButton;
+
+#[derive(Component)]
+pub struct MultiplayerButton;
+
+// marker for the local multiplayer lobby and everything on it
+#[derive(Component)]
+pub struct LobbyMenu;
+
+// the lobby's list of joined players, rewritten by `update_lobby`
+#[derive(Component)]
+pub struct LobbyText;
+
+// marker for the main menu and its buttons
+#[derive(Component)]
+pub struct MainMenu;
+
+#[derive(Event, Default)]
+pub struct RestartEvent;
+
+#[derive(Event)]
+pub struct UpgradeIconClickedEvent {
+    icon: UpgradeIcon,
+}
+
+/// Sent whenever the player tries to buy an upgrade from the menu, whether it worked or not
+#[derive(Event)]
+pub struct UpgradePurchaseEvent {
+    pub upgrade_id: usize,
+    pub outcome: PurchaseOutcome,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PurchaseOutcome {
+    Bought,
+    TooExpensive,
+    MaxedOut,
+}
+
+#[derive(Resource, Clone, Copy)]
+pub enum GameOverMenuSelectedButton {
+    Restart,
+    Quit,
+    None,
+}
+
+#[derive(Resource, Clone, PartialEq, Eq)]
+pub enum PauseMenuSelectedButton {
+    None,
+    Upgrades,
+    Settings,
+    SaveAndQuit,
+    Quit,
+}
+
+impl PauseMenuSelectedButton {
+    fn next(&mut self) {
+        *self = match *self {
+            Self::None => Self::Upgrades,
+            Self::Upgrades => Self::Settings,
+            Self::Settings => Self::SaveAndQuit,
+            Self::SaveAndQuit => Self::Quit,
+            Self::Quit => Self::None,
+        }
+    }
+
+    fn prev(&mut self) {
+        *self = match *self {
+            Self::None => Self::Quit,
+            Self::Upgrades => Self::None,
+            Self::Settings => Self::Upgrades,
+            Self::SaveAndQuit => Self::Settings,
+            Self::Quit => Self::SaveAndQuit,
+        }
+    }
+}
+
+// which pause menu entry a button is, so it can be underlined when selected
+#[derive(Component)]
+pub struct PauseMenuButton(PauseMenuSelectedButton);
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuSelectedButton {
+    Continue,
+    Play,
+    Multiplayer,
+    Quit,
+}
+
+impl MainMenuSelectedButton {
+    // moves through the buttons that are actually on screen, wrapping around at either end
+    fn step(&mut self, available: &[Self], forwards: bool) {
+        let Some(index) = available.iter().position(|button| button == self) else {
+            if let Some(first) = available.first() {
+                *self = *first;
+            }
+            return;
+        };
+        let next_index = if forwards {
+            (index + 1) % available.len()
+        } else {
+            (index + available.len() - 1) % available.len()
+        };
+        *self = available[next_index];
+    }
+}
+
+// which main menu entry a button is, so it can be underlined when selected
+#[derive(Component)]
+pub struct MainMenuButton(MainMenuSelectedButton);
+
+// how far apart the nodes of a tail are kept, see `move_tail`
+const TAIL_NODE_GAP: f32 = 50.;
+
+/// How much health a snake can have, raised by the players' max health upgrades
+#[derive(Component, Clone, Copy)]
+pub struct SnakeMaxHealth(f32);
+
+impl Default for SnakeMaxHealth {
+    fn default() -> Self {
+        Self(100.)
+    }
+}
+
+pub const FOOD_HEALTH: f32 = 30.;
+
+// each node's place in the tail, counting from the head
+#[derive(Component)]
+pub struct TailNodeCount(pub usize);
+
+// the snake a tail node belongs to
+#[derive(Component)]
+pub struct TailOf(pub Entity);
+
+pub fn tail_node_bundle(
+    owner: Entity,
+    translation: Vec3,
+    ignore_collision: bool,
+    index: usize,
+) -> impl Bundle {
+    (
+        SnakeTailNode(ignore_collision),
+        TailOf(owner),
+        TransformBundle::from_transform(Transform::from_translation(translation)),
+        PhysicalTranslation::new(translation),
+        TailNodeCount(index),
+    )
+}
+
+#[derive(Component)]
+pub struct DebugOutput;
+
+#[derive(Component)]
+pub struct CoinsOutput;
+
+#[derive(Component)]
+pub struct CoinbagValueOutput;
+
+#[derive(Asset, AsBindGroup, TypePath, Clone, Default)]
+pub struct GameFieldMaterial {
+    #[uniform(0)]
+    width: f32,
+    #[uniform(1)]
+    height: f32,
+    #[texture(2)]
+    #[sampler(3)]
+    texture: Handle<Image>,
+}
+
+impl Material2d for GameFieldMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/gamefield.wgsl".into()
+    }
+}
+
+#[derive(Component)]
+pub struct GameField;
+
+#[derive(Component)]
+pub struct AbilityOutput;
+
+#[derive(Component)]
+pub struct ScoreOutput;
+
+// one per kind of status effect, next to the score
+#[derive(Component)]
+pub struct StatusEffectIcon(pub StatusEffect);
+
+#[derive(Component)]
+pub struct StatusEffectLabel(pub StatusEffect);
+
+// the bar under a status effect's icon, as wide as the time it has left
+#[derive(Component)]
+pub struct StatusEffectBar(pub StatusEffect);
+
+#[derive(Component)]
+pub struct Snake {
+    pub health: f32,
+    // index into `LocalPlayers`
+    pub player: usize,
+    pub score: usize,
+}
+
+impl Snake {
+    /// A snake at the start of a run, that hasn't eaten anything yet
+    pub fn new(player: usize, health: f32) -> Self {
+        Self {
+            health,
+            player,
+            score: 0,
+        }
+    }
+}
+
+pub fn snake_bundle(
+    snake: Snake,
+    translation: Vec3,
+    velocity: Vec3,
+    cooldowns: AbilityCooldowns,
+    status_effects: StatusEffects,
+) -> impl Bundle {
+    (
+        snake,
+        SnakeControls::default(),
+        SnakeSpeed::default(),
+        SnakeMaxHealth::default(),
+        cooldowns,
+        status_effects,
+        TransformBundle::from_transform(Transform::from_translation(translation)),
+        PhysicalTranslation::new(translation),
+        Velocity(velocity),
+    )
+}
+
+/// Which device each player steers with, in player order.  Each of them gets a snake
+#[derive(Resource)]
+pub struct LocalPlayers {
+    pub devices: Vec<InputDevice>,
+}
+
+impl Default for LocalPlayers {
+    fn default() -> Self {
+        Self {
+            devices: vec![InputDevice::Any],
+        }
+    }
+}
+
+pub const MAX_LOCAL_PLAYERS: usize = 4;
+
+/// The colour each player's snake is drawn in.  Player 1 keeps the original green
+pub fn player_color(player: usize) -> Color {
+    const COLORS: [Color; MAX_LOCAL_PLAYERS] =
+        [Color::GREEN, Color::ORANGE, Color::CYAN, Color::FUCHSIA];
+    COLORS[player % COLORS.len()]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeathCause {
+    Starved,
+    HitWall,
+    HitOwnTail,
+    // the index of the player whose tail it was
+    HitOtherSnake(usize),
+    // ate something bad on an empty stomach
+    Poisoned,
+    // ran into a rock or wall, or one log too many
+    HitObstacle(ObstacleKind),
+}
+
+impl fmt::Display for DeathCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Starved => write!(f, "starved"),
+            Self::HitWall => write!(f, "hit the wall"),
+            Self::HitOwnTail => write!(f, "hit own tail"),
+            Self::HitOtherSnake(player) => write!(f, "hit P{}'s tail", player + 1),
+            Self::Poisoned => write!(f, "poisoned"),
+            Self::HitObstacle(kind) => write!(f, "ran into a {kind}"),
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct SnakeDiedEvent {
+    pub snake: Entity,
+    pub cause: DeathCause,
+}
+
+/// How one player's round went
+#[derive(Clone, Debug)]
+pub struct PlayerResult {
+    pub player: usize,
+    pub score: usize,
+    pub tail_length: usize,
+    // simulation ticks into the run when they died, or when the round ended for survivors
+    pub ticks: u64,
+    // `None` if they were still alive at the end
+    pub cause: Option<DeathCause>,
+}
+
+/// Every player's result for the current round, in the order they were knocked out
+#[derive(Resource, Default)]
+pub struct RoundResults {
+    pub results: Vec<PlayerResult>,
+    // the round ended because the level's goal was reached
+    pub level_complete: bool,
+}
+
+impl RoundResults {
+    /// Best placed first: survivors, then whoever lasted longest, then by score
+    pub fn ranked(&self) -> Vec<&PlayerResult> {
+        let mut ranked: Vec<_> = self.results.iter().collect();
+        ranked.sort_by(|a, b| match (a.cause, b.cause) {
+            (None, None) => b.score.cmp(&a.score),
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(_), Some(_)) => b.ticks.cmp(&a.ticks).then(b.score.cmp(&a.score)),
+        });
+        ranked
+    }
+}
+
+/// What whoever is steering a snake wants it to do.  Filled in from the keyboard and gamepad by
+/// `player_input`, or written directly when running headless.
+#[derive(Component, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SnakeControls {
+    /// digital direction, e.g from the arrow keys.  Normalised before it is applied
+    pub direction: Vec2,
+    /// analog stick position, applied as-is
+    pub stick: Vec2,
+    /// an ability to use on the next tick.  Cleared by `use_abilities` once it's been tried
+    pub ability: Option<Ability>,
+}
+
+/// Something a snake can do on demand, once it's been bought from the upgrades menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Ability {
+    // a burst of speed in whatever direction the snake is already going
+    Dash,
+    // stops dead
+    Brake,
+    // drops the back half of the tail
+    ShedTail,
+}
+
+impl Ability {
+    pub const ALL: [Self; 3] = [Self::Dash, Self::Brake, Self::ShedTail];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn action(self) -> Action {
+        match self {
+            Self::Dash => Action::Dash,
+            Self::Brake => Action::Brake,
+            Self::ShedTail => Action::ShedTail,
+        }
+    }
+
+    /// How many ticks after it's used before it can be used again
+    pub fn cooldown_ticks(self) -> u64 {
+        let seconds = match self {
+            Self::Dash => 3.,
+            Self::Brake => 2.,
+            Self::ShedTail => 10.,
+        };
+        (seconds * SIMULATION_HZ) as u64
+    }
+}
+
+impl fmt::Display for Ability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dash => write!(f, "Dash"),
+            Self::Brake => write!(f, "Brake"),
+            Self::ShedTail => write!(f, "Shed Tail"),
+        }
+    }
+}
+
+/// How many ticks each of a snake's abilities has left to cool down
+#[derive(Component, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AbilityCooldowns([u64; Ability::ALL.len()]);
+
+impl AbilityCooldowns {
+    pub fn remaining(&self, ability: Ability) -> u64 {
+        self.0[ability.index()]
+    }
+
+    pub fn ready(&self, ability: Ability) -> bool {
+        self.remaining(ability) == 0
+    }
+
+    pub fn start(&mut self, ability: Ability) {
+        self.0[ability.index()] = ability.cooldown_ticks();
+    }
+
+    /// One tick closer to every ability being ready
+    pub fn tick(&mut self) {
+        for remaining in &mut self.0 {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+}
+
+/// Something temporarily changing how a snake plays, e.g from what it's eaten
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum StatusEffect {
+    SpeedBoost,
+    Slowed,
+    // can't die by running into walls or tails
+    Invulnerable,
+    // doesn't get hungry
+    HungerFree,
+    // pulls nearby food and coin bags in
+    Magnet,
+}
+
+impl StatusEffect {
+    /// In the order they're shown on the HUD
+    pub const ALL: [Self; 5] = [
+        Self::SpeedBoost,
+        Self::Slowed,
+        Self::Invulnerable,
+        Self::HungerFree,
+        Self::Magnet,
+    ];
+
+    /// What it's called on its HUD icon
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SpeedBoost => "SPD",
+            Self::Slowed => "SLO",
+            Self::Invulnerable => "INV",
+            Self::HungerFree => "FED",
+            Self::Magnet => "MAG",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Self::SpeedBoost => Color::rgb(0.2, 0.6, 1.0),
+            Self::Slowed => Color::rgb(0.5, 0.5, 0.6),
+            Self::Invulnerable => Color::GOLD,
+            Self::HungerFree => Color::rgb(0.3, 0.8, 0.3),
+            Self::Magnet => Color::rgb(0.8, 0.2, 0.2),
+        }
+    }
+}
+
+// how much each speed boost or slow changes a snake's acceleration by.  They stack
+const SPEED_BOOST_FACTOR: f32 = 1.5;
+const SLOWED_FACTOR: f32 = 0.6;
+// however many are stacked up, so a boosted snake can't outrun its own collision checks
+const MIN_SPEED_MULTIPLIER: f32 = 0.25;
+const MAX_SPEED_MULTIPLIER: f32 = 2.;
+
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+struct TimedStatusEffect {
+    effect: StatusEffect,
+    // ticks, counted down by `tick_status_effects`
+    remaining: u64,
+    duration: u64,
+}
+
+/// The status effects on a snake and how long each has left.  The same effect can be on it more
+/// than once, each wearing off on its own.
+///
+/// They're counted down in simulation ticks, so they stop while the game is paused.
+#[derive(Component, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StatusEffects {
+    active: Vec<TimedStatusEffect>,
+}
+
+impl StatusEffects {
+    pub fn add(&mut self, effect: StatusEffect, seconds: f32) {
+        let duration = (seconds as f64 * SIMULATION_HZ) as u64;
+        if duration > 0 {
+            self.active.push(TimedStatusEffect {
+                effect,
+                remaining: duration,
+                duration,
+            });
+        }
+    }
+
+    pub fn has(&self, effect: StatusEffect) -> bool {
+        self.stacks(effect) > 0
+    }
+
+    /// How many times over the effect is on
+    pub fn stacks(&self, effect: StatusEffect) -> usize {
+        self.active
+            .iter()
+            .filter(|timed| timed.effect == effect)
+            .count()
+    }
+
+    /// What's left of the longest lasting stack of the effect, from 1 down to 0
+    pub fn remaining_fraction(&self, effect: StatusEffect) -> Option<f32> {
+        self.active
+            .iter()
+            .filter(|timed| timed.effect == effect)
+            .map(|timed| timed.remaining as f32 / timed.duration as f32)
+            .max_by(f32::total_cmp)
+    }
+
+    /// How much faster (or slower) than normal the snake accelerates
+    pub fn speed_multiplier(&self) -> f32 {
+        let boosts = self.stacks(StatusEffect::SpeedBoost) as i32;
+        let slows = self.stacks(StatusEffect::Slowed) as i32;
+        if boosts == 0 && slows == 0 {
+            return 1.;
+        }
+        (SPEED_BOOST_FACTOR.powi(boosts) * SLOWED_FACTOR.powi(slows))
+            .clamp(MIN_SPEED_MULTIPLIER, MAX_SPEED_MULTIPLIER)
+    }
+
+    /// One tick closer to everything wearing off
+    pub fn tick(&mut self) {
+        for timed in &mut self.active {
+            timed.remaining = timed.remaining.saturating_sub(1);
+        }
+        self.active.retain(|timed| timed.remaining > 0);
+    }
+}
+
+#[derive(Component)]
+pub struct SnakeTailNode(bool);
+
+#[derive(Asset, AsBindGroup, TypePath, Clone)]
+pub struct HealthbarMaterial {
+    #[uniform(0)]
+    health: f32,
+}
+
+impl Material2d for HealthbarMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/healthbar.wgsl".into()
+    }
+}
+
+#[derive(Component)]
+pub struct Velocity(pub Vec3);
+
+/// Where an entity that moves in `FixedUpdate` was at the last two simulation ticks.
+///
+/// The simulation works on `Transform` as normal; this just lets rendering interpolate between
+/// ticks.  Anything that moves such an entity outside of `FixedUpdate` must [`teleport`] it too,
+/// or the move will be undone on the next tick.
+///
+/// [`teleport`]: PhysicalTranslation::teleport
+#[derive(Component)]
+pub struct PhysicalTranslation {
+    previous: Vec3,
+    current: Vec3,
+}
+
+impl PhysicalTranslation {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+        }
+    }
+
+    pub fn teleport(&mut self, translation: Vec3) {
+        *self = Self::new(translation);
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Obstacle {
+    pub kind: ObstacleKind,
+    pub shape: ObstacleShape,
+}
+
+#[derive(Component)]
+pub struct Food {
+    pub kind: FoodKind,
+    // the tick it turned up on.  At most one piece spawns a tick, so this puts them in a fixed order
+    pub spawned: u64,
+}
+
+/// How much a piece of food has noticed the snakes around it, see `update_food_awareness`
+#[derive(Component, Default)]
+pub struct Awareness {
+    // from 0 for oblivious, to 1 for when it notices and runs
+    pub level: f32,
+    // running away, until it calms down again
+    pub alerted: bool,
+}
+
+// the "?" or "!" over a piece of food
+#[derive(Component)]
+pub struct FoodAlert(pub Entity);
+
+impl Awareness {
+    /// Whether it's noticed something, but not enough to run yet
+    pub fn suspicious(&self) -> bool {
+        !self.alerted && self.level >= 0.5
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct UpgradeIcon {
+    upgrade_id: usize,
+}
+
+// "Lv 2/5" under an upgrade in the upgrades menu
+#[derive(Component)]
+pub struct UpgradeLevelText {
+    upgrade_id: usize,
+}
+
+// what the next level of an upgrade costs, in the upgrades menu
+#[derive(Component)]
+pub struct UpgradePriceText {
+    upgrade_id: usize,
+}
+
+// the picture on an upgrade's button, greyed out while it can't be bought
+#[derive(Component)]
+pub struct UpgradeIconImage {
+    upgrade_id: usize,
+}
+
+// shakes an upgrade's button after a purchase of it fails, for this many seconds so far
+#[derive(Component, Default)]
+pub struct UpgradeIconShake(f32);
+
+// the name of the selected upgrade, at the top of the details panel
+#[derive(Component)]
+pub struct UpgradeDetailTitle;
+
+// everything else about the selected upgrade: what it does, its level and price
+#[derive(Component)]
+pub struct UpgradeDetailText;
+
+// what happened when the last upgrade was bought, or wasn't
+#[derive(Component)]
+pub struct PurchaseFeedbackText;
+
+#[derive(AsBindGroup, Asset, TypePath, Clone)]
+pub struct IconHoverEffectMaterial {
+    upgrade_id: usize,
+    #[uniform(0)]
+    color: Vec4,
+    #[uniform(1)]
+    highlight: u32, // actually a bool in disguise 🤫
+}
+
+impl UiMaterial for IconHoverEffectMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/icon_hover_effect.wgsl".into()
+    }
+}
+
+// how many random spots to try before giving up on spawning something this tick
+pub const SPAWN_ATTEMPTS: usize = 10;
+
+pub fn food_bundle(
+    kind: FoodKind,
+    spawned: u64,
+    translation: Vec3,
+    velocity: Vec3,
+    rotation: Quat,
+    awareness: Awareness,
+) -> impl Bundle {
+    // the sprite is drawn for a mouse the size of `FOOD_RADIUS`
+    let size = 0.3 * kind.stats().radius / FOOD_RADIUS;
+    (
+        Food { kind, spawned },
+        awareness,
+        Velocity(velocity),
+        PhysicalTranslation::new(translation),
+        TransformBundle::from_transform(Transform {
+            translation,
+            rotation,
+            scale: Vec3::new(size, size, 1.0),
+        }),
+    )
+}
+
+pub fn obstacle_bundle(placement: ObstaclePlacement) -> impl Bundle {
+    (
+        Obstacle {
+            kind: placement.kind,
+            shape: placement.shape,
+        },
+        TransformBundle::from_transform(Transform::from_translation(
+            placement.position.extend(OBSTACLE_LAYER),
+        )),
+    )
+}
+
+pub fn coin_bag_bundle(translation: Vec3, value: f32) -> impl Bundle {
+    (
+        CoinBag { value },
+        TransformBundle::from_transform(Transform {
+            translation,
+            scale: Vec3::new(0.3, 0.3, 1.0),
+            ..default()
+        }),
+    )
+}
+
+#[derive(Resource, Default)]
+pub struct Game {
+    pub score: usize,
+    pub coins: f32,
+    // simulation ticks elapsed this run
+    pub ticks: u64,
+    // ids of the upgrades bought this run, in the order they were bought
+    pub upgrades: Vec<usize>,
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn restart(&mut self) {
+        *self = Self::new()
+    }
+}
+
+#[derive(Resource)]
+pub struct CameraSettings {
+    follow_snake: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ticks until something added for `seconds` wears off
+    fn ticks(seconds: f32) -> u64 {
+        (seconds as f64 * SIMULATION_HZ) as u64
+    }
+
+    #[test]
+    fn status_effects_wear_off_after_their_duration() {
+        let mut effects = StatusEffects::default();
+        effects.add(StatusEffect::Magnet, 1.);
+        assert!(effects.has(StatusEffect::Magnet));
+        assert_eq!(effects.remaining_fraction(StatusEffect::Magnet), Some(1.));
+        for _ in 1..ticks(1.) {
+            effects.tick();
+        }
+        assert!(effects.has(StatusEffect::Magnet));
+        effects.tick();
+        assert!(!effects.has(StatusEffect::Magnet));
+        assert_eq!(effects.remaining_fraction(StatusEffect::Magnet), None);
+    }
+
+    #[test]
+    fn status_effects_too_short_for_a_tick_are_ignored() {
+        let mut effects = StatusEffects::default();
+        effects.add(StatusEffect::Invulnerable, 0.);
+        assert!(!effects.has(StatusEffect::Invulnerable));
+    }
+
+    #[test]
+    fn status_effects_stack_and_wear_off_separately() {
+        let mut effects = StatusEffects::default();
+        effects.add(StatusEffect::HungerFree, 1.);
+        effects.add(StatusEffect::HungerFree, 2.);
+        effects.add(StatusEffect::Slowed, 2.);
+        assert_eq!(effects.stacks(StatusEffect::HungerFree), 2);
+        assert_eq!(effects.stacks(StatusEffect::Slowed), 1);
+        for _ in 0..ticks(1.) {
+            effects.tick();
+        }
+        assert_eq!(effects.stacks(StatusEffect::HungerFree), 1);
+        // the longest lasting stack is what's shown
+        assert_eq!(
+            effects.remaining_fraction(StatusEffect::HungerFree),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn speed_multiplier_stacks_and_is_clamped() {
+        let mut effects = StatusEffects::default();
+        assert_eq!(effects.speed_multiplier(), 1.);
+        effects.add(StatusEffect::SpeedBoost, 1.);
+        assert_eq!(effects.speed_multiplier(), SPEED_BOOST_FACTOR);
+        effects.add(StatusEffect::Slowed, 1.);
+        assert_eq!(
+            effects.speed_multiplier(),
+            SPEED_BOOST_FACTOR * SLOWED_FACTOR
+        );
+        for _ in 0..5 {
+            effects.add(StatusEffect::SpeedBoost, 1.);
+        }
+        assert_eq!(effects.speed_multiplier(), MAX_SPEED_MULTIPLIER);
+        for _ in 0..10 {
+            effects.add(StatusEffect::Slowed, 1.);
+        }
+        assert_eq!(effects.speed_multiplier(), MIN_SPEED_MULTIPLIER);
+    }
+}
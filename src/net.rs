@@ -0,0 +1,603 @@
+//! Online head-to-head matches over UDP.
+//!
+//! Both peers run the whole simulation, and only send each other their inputs.  Every input is
+//! scheduled [`INPUT_DELAY`] ticks ahead, and a tick only runs once both players' inputs for it
+//! have arrived (lockstep), so the simulations stay identical as long as they're deterministic.
+//! Checksums of the game state are swapped every [`CHECKSUM_INTERVAL`] ticks to catch them when
+//! they aren't.
+//!
+//! The host decides when each round starts and what its seed is, so rematches stay in step too.
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt, io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Ability, Rivals, SnakeControls};
+
+// bump this whenever the protocol or the simulation changes, so mismatched builds refuse to play
+pub const NET_PROTOCOL_VERSION: u32 = 9;
+
+/// How many ticks after it's pressed an input takes effect, to hide the round trip to the peer
+pub const INPUT_DELAY: u64 = 3;
+
+/// How often, in ticks, the peers compare game states
+pub const CHECKSUM_INTERVAL: u64 = 30;
+
+// how long without hearing from the peer before giving up on them
+const PEER_TIMEOUT: Duration = Duration::from_secs(5);
+// how often handshake and round start messages are repeated until they get an answer
+const RESEND_INTERVAL: Duration = Duration::from_millis(250);
+// inputs are resent until acknowledged, but never more than this many in one packet
+const MAX_INPUTS_PER_PACKET: usize = 32;
+// large enough for a packet full of inputs
+const MAX_PACKET_SIZE: usize = 4096;
+
+/// One tick of a player's controls.  Floats are sent as their bits, so both peers apply exactly
+/// the same values
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetInput {
+    direction: (u32, u32),
+    stick: (u32, u32),
+    ability: Option<Ability>,
+}
+
+impl NetInput {
+    pub fn new(controls: &SnakeControls) -> Self {
+        Self {
+            direction: (
+                controls.direction.x.to_bits(),
+                controls.direction.y.to_bits(),
+            ),
+            stick: (controls.stick.x.to_bits(), controls.stick.y.to_bits()),
+            ability: controls.ability,
+        }
+    }
+
+    pub fn apply(&self, controls: &mut SnakeControls) {
+        controls.direction = Vec2::new(
+            f32::from_bits(self.direction.0),
+            f32::from_bits(self.direction.1),
+        );
+        controls.stick = Vec2::new(f32::from_bits(self.stick.0), f32::from_bits(self.stick.1));
+        controls.ability = self.ability;
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum NetMessage {
+    // client to host, until it's answered
+    Hello {
+        version: u32,
+    },
+    Welcome {
+        version: u32,
+    },
+    // host to client, until the client's inputs for the round show up
+    StartRound {
+        round: u32,
+        seed: u64,
+        rivals: Rivals,
+        level: usize,
+    },
+    Inputs {
+        round: u32,
+        // every tick up to and including this one has arrived, so the sender can stop resending
+        // them.  `None` if nothing has yet
+        received_up_to: Option<u64>,
+        inputs: Vec<(u64, NetInput)>,
+    },
+    Checksum {
+        round: u32,
+        tick: u64,
+        checksum: u64,
+    },
+    Bye,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetRole {
+    Host,
+    Client,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetPhase {
+    // host waiting for someone to join, or client waiting for the host to answer
+    Connecting,
+    Connected,
+    // the peer left, timed out or turned out to be incompatible
+    Disconnected,
+}
+
+#[derive(Debug)]
+pub enum NetError {
+    Io(io::Error),
+    Address(String),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "network error: {e}"),
+            Self::Address(address) => write!(f, "could not resolve {address}"),
+        }
+    }
+}
+
+/// Things the session noticed while reading packets, for the systems to act on
+#[derive(Debug, PartialEq)]
+pub enum NetEvent {
+    Connected,
+    // the host started a round, the client should restart with this seed, on this level
+    StartRound {
+        seed: u64,
+        rivals: Rivals,
+        level: usize,
+    },
+    // the two simulations disagree about the state on this tick
+    Desync {
+        tick: u64,
+    },
+    Disconnected,
+}
+
+/// An online match in progress.  Present only while hosting or joining one
+#[derive(Resource)]
+pub struct NetSession {
+    socket: UdpSocket,
+    role: NetRole,
+    phase: NetPhase,
+    peer: Option<SocketAddr>,
+    last_heard: Instant,
+    last_resend: Option<Instant>,
+    // increases every time the host starts a round, so stray packets from old ones are ignored
+    round: u32,
+    // the host's current round has been seen by the client
+    round_acknowledged: bool,
+    seed: u64,
+    // this player's controls as of the last frame, before any delay
+    latest_local: SnakeControls,
+    local_inputs: BTreeMap<u64, NetInput>,
+    remote_inputs: BTreeMap<u64, NetInput>,
+    // every remote input up to this tick has arrived
+    received_up_to: Option<u64>,
+    // the latest tick the peer has told us it has all our inputs up to
+    peer_received_up_to: Option<u64>,
+    // whether both players' inputs were there for the current tick
+    tick_ready: bool,
+    local_checksums: BTreeMap<u64, u64>,
+    remote_checksums: BTreeMap<u64, u64>,
+    desynced: bool,
+    events: Vec<NetEvent>,
+    // fraction of outgoing packets to drop on purpose, for testing
+    packet_loss: f32,
+}
+
+impl NetSession {
+    /// Waits for a player to join on `port`
+    pub fn host(port: u16) -> Result<Self, NetError> {
+        let socket = UdpSocket::bind(("0.0.0.0", port)).map_err(NetError::Io)?;
+        Self::new(socket, NetRole::Host, None)
+    }
+
+    /// Joins a match hosted at `address`, e.g `127.0.0.1:7777`
+    pub fn join(address: &str) -> Result<Self, NetError> {
+        let peer = address
+            .to_socket_addrs()
+            .map_err(NetError::Io)?
+            .next()
+            .ok_or_else(|| NetError::Address(address.to_string()))?;
+        let local = if peer.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local).map_err(NetError::Io)?;
+        Self::new(socket, NetRole::Client, Some(peer))
+    }
+
+    fn new(socket: UdpSocket, role: NetRole, peer: Option<SocketAddr>) -> Result<Self, NetError> {
+        socket.set_nonblocking(true).map_err(NetError::Io)?;
+        Ok(Self {
+            socket,
+            role,
+            phase: NetPhase::Connecting,
+            peer,
+            last_heard: Instant::now(),
+            last_resend: None,
+            round: 0,
+            round_acknowledged: false,
+            seed: 0,
+            latest_local: SnakeControls::default(),
+            local_inputs: BTreeMap::new(),
+            remote_inputs: BTreeMap::new(),
+            received_up_to: None,
+            peer_received_up_to: None,
+            tick_ready: false,
+            local_checksums: BTreeMap::new(),
+            remote_checksums: BTreeMap::new(),
+            desynced: false,
+            events: Vec::new(),
+            packet_loss: 0.,
+        })
+    }
+
+    pub fn role(&self) -> NetRole {
+        self.role
+    }
+
+    pub fn phase(&self) -> NetPhase {
+        self.phase
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Which player this side steers: the host is player 1, whoever joined is player 2
+    pub fn local_player(&self) -> usize {
+        match self.role {
+            NetRole::Host => 0,
+            NetRole::Client => 1,
+        }
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn desynced(&self) -> bool {
+        self.desynced
+    }
+
+    pub fn tick_ready(&self) -> bool {
+        self.tick_ready
+    }
+
+    /// Drops this fraction of outgoing packets, to check the game copes with a bad connection
+    pub fn set_packet_loss(&mut self, packet_loss: f32) {
+        self.packet_loss = packet_loss;
+    }
+
+    /// Everything that has happened since the last call
+    pub fn take_events(&mut self) -> Vec<NetEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn send(&self, message: &NetMessage) {
+        let Some(peer) = self.peer else {
+            return;
+        };
+        if self.packet_loss > 0. && rand::random::<f32>() < self.packet_loss {
+            return;
+        }
+        let packet = match ron::to_string(message) {
+            Ok(packet) => packet,
+            Err(e) => {
+                warn!("Could not encode {message:?}: {e}");
+                return;
+            }
+        };
+        // packets get lost all the time, everything that matters is resent until it arrives
+        if let Err(e) = self.socket.send_to(packet.as_bytes(), peer) {
+            if e.kind() != io::ErrorKind::WouldBlock {
+                warn!("Could not send to {peer}: {e}");
+            }
+        }
+    }
+
+    /// Reads every packet that has arrived.  Anything the game needs to act on shows up in
+    /// [`take_events`](Self::take_events)
+    pub fn poll(&mut self) {
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        loop {
+            let (length, from) = match self.socket.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                // on some platforms an unreachable peer shows up as an error on the next read
+                Err(e) if e.kind() == io::ErrorKind::ConnectionReset => continue,
+                Err(e) => {
+                    warn!("Network error: {e}");
+                    break;
+                }
+            };
+            let message = std::str::from_utf8(&buffer[..length])
+                .ok()
+                .and_then(|packet| ron::from_str::<NetMessage>(packet).ok());
+            let Some(message) = message else {
+                warn!("Ignoring a garbled packet from {from}");
+                continue;
+            };
+            self.receive(from, message);
+        }
+        if self.phase == NetPhase::Connected && self.last_heard.elapsed() > PEER_TIMEOUT {
+            warn!("Lost the connection to the other player");
+            self.phase = NetPhase::Disconnected;
+            self.events.push(NetEvent::Disconnected);
+        }
+    }
+
+    fn receive(&mut self, from: SocketAddr, message: NetMessage) {
+        // a host only ever plays against whoever said hello first
+        if self.role == NetRole::Host && self.peer.is_none() {
+            if let NetMessage::Hello { version } = message {
+                if version != NET_PROTOCOL_VERSION {
+                    warn!("{from} is running an incompatible version (v{version}, expected v{NET_PROTOCOL_VERSION})");
+                    return;
+                }
+                info!("{from} joined");
+                self.peer = Some(from);
+                self.phase = NetPhase::Connected;
+                self.last_heard = Instant::now();
+                self.send(&NetMessage::Welcome {
+                    version: NET_PROTOCOL_VERSION,
+                });
+                self.events.push(NetEvent::Connected);
+            }
+            return;
+        }
+        if Some(from) != self.peer || self.phase == NetPhase::Disconnected {
+            return;
+        }
+        self.last_heard = Instant::now();
+        match message {
+            // our welcome got lost
+            NetMessage::Hello { .. } => self.send(&NetMessage::Welcome {
+                version: NET_PROTOCOL_VERSION,
+            }),
+            NetMessage::Welcome { version } => {
+                if self.phase != NetPhase::Connecting {
+                    return;
+                }
+                if version != NET_PROTOCOL_VERSION {
+                    warn!("The host is running an incompatible version (v{version}, expected v{NET_PROTOCOL_VERSION})");
+                    self.phase = NetPhase::Disconnected;
+                    self.events.push(NetEvent::Disconnected);
+                    return;
+                }
+                info!("Joined {from}");
+                self.phase = NetPhase::Connected;
+                self.events.push(NetEvent::Connected);
+            }
+            NetMessage::StartRound {
+                round,
+                seed,
+                rivals,
+                level,
+            } => {
+                if self.role == NetRole::Client && round > self.round {
+                    // the host only starts a round once it has let us in, so our welcome got lost
+                    if self.phase == NetPhase::Connecting {
+                        info!("Joined {from}");
+                        self.phase = NetPhase::Connected;
+                        self.events.push(NetEvent::Connected);
+                    }
+                    self.begin_round(round, seed);
+                    self.events.push(NetEvent::StartRound {
+                        seed,
+                        rivals,
+                        level,
+                    });
+                }
+            }
+            NetMessage::Inputs {
+                round,
+                received_up_to,
+                inputs,
+            } => {
+                if round != self.round {
+                    return;
+                }
+                self.round_acknowledged = true;
+                if received_up_to > self.peer_received_up_to {
+                    self.peer_received_up_to = received_up_to;
+                }
+                for (tick, input) in inputs {
+                    if Some(tick) > self.received_up_to {
+                        self.remote_inputs.entry(tick).or_insert(input);
+                    }
+                }
+                self.advance_received_up_to();
+            }
+            NetMessage::Checksum {
+                round,
+                tick,
+                checksum,
+            } => {
+                if round != self.round {
+                    return;
+                }
+                self.remote_checksums.insert(tick, checksum);
+                self.check(tick);
+            }
+            NetMessage::Bye => {
+                info!("The other player left");
+                self.phase = NetPhase::Disconnected;
+                self.events.push(NetEvent::Disconnected);
+            }
+        }
+    }
+
+    /// Keeps the handshake, round start and the last inputs of a round going through packet loss.
+    /// Call every frame
+    pub fn resend(&mut self, rivals: Rivals, level: usize) {
+        let due = self
+            .last_resend
+            .map_or(true, |last_resend| last_resend.elapsed() >= RESEND_INTERVAL);
+        if !due {
+            return;
+        }
+        self.last_resend = Some(Instant::now());
+        match (self.role, self.phase) {
+            (NetRole::Client, NetPhase::Connecting) => self.send(&NetMessage::Hello {
+                version: NET_PROTOCOL_VERSION,
+            }),
+            (NetRole::Host, NetPhase::Connected) if self.round > 0 && !self.round_acknowledged => {
+                self.send(&NetMessage::StartRound {
+                    round: self.round,
+                    seed: self.seed,
+                    rivals,
+                    level,
+                });
+            }
+            _ => {}
+        }
+        // once this side's round is over it stops exchanging inputs every tick, but the peer may
+        // still be waiting on the last few
+        if self.phase == NetPhase::Connected && self.round > 0 {
+            self.send_inputs();
+        }
+    }
+
+    /// Host only: starts the next round with a fresh seed, which is returned
+    pub fn start_round(&mut self, rivals: Rivals, level: usize) -> u64 {
+        let seed = rand::random();
+        self.begin_round(self.round + 1, seed);
+        self.send(&NetMessage::StartRound {
+            round: self.round,
+            seed,
+            rivals,
+            level,
+        });
+        self.last_resend = Some(Instant::now());
+        seed
+    }
+
+    fn begin_round(&mut self, round: u32, seed: u64) {
+        self.round = round;
+        self.seed = seed;
+        self.round_acknowledged = false;
+        self.local_inputs.clear();
+        self.remote_inputs.clear();
+        self.received_up_to = None;
+        self.peer_received_up_to = None;
+        self.tick_ready = false;
+        self.local_checksums.clear();
+        self.remote_checksums.clear();
+        self.desynced = false;
+        // nobody has had the chance to press anything for the first few ticks
+        for tick in 0..INPUT_DELAY {
+            self.local_inputs.insert(tick, NetInput::default());
+            self.remote_inputs.insert(tick, NetInput::default());
+        }
+        self.advance_received_up_to();
+    }
+
+    fn advance_received_up_to(&mut self) {
+        let mut next = self.received_up_to.map_or(0, |tick| tick + 1);
+        while self.remote_inputs.contains_key(&next) {
+            self.received_up_to = Some(next);
+            next += 1;
+        }
+    }
+
+    pub fn set_latest_local(&mut self, controls: &SnakeControls) {
+        self.latest_local = SnakeControls {
+            direction: controls.direction,
+            stick: controls.stick,
+            // held onto until it's been scheduled, however many frames go by without a tick
+            ability: controls.ability.or(self.latest_local.ability),
+        };
+    }
+
+    /// Schedules this player's latest input for `tick + INPUT_DELAY`, and sends everything the
+    /// peer hasn't got yet
+    pub fn exchange_inputs(&mut self, tick: u64) {
+        if let Entry::Vacant(entry) = self.local_inputs.entry(tick + INPUT_DELAY) {
+            entry.insert(NetInput::new(&self.latest_local));
+            // an ability is used once per press, not on every tick after it
+            self.latest_local.ability = None;
+        }
+        self.send_inputs();
+    }
+
+    fn send_inputs(&self) {
+        let inputs: Vec<_> = self
+            .local_inputs
+            .iter()
+            .filter(|(input_tick, _)| Some(**input_tick) > self.peer_received_up_to)
+            .take(MAX_INPUTS_PER_PACKET)
+            .map(|(input_tick, input)| (*input_tick, *input))
+            .collect();
+        self.send(&NetMessage::Inputs {
+            round: self.round,
+            received_up_to: self.received_up_to,
+            inputs,
+        });
+    }
+
+    /// Both players' inputs for `tick`, local first, once they've both arrived.  Until they
+    /// have, the simulation waits
+    pub fn inputs_for(&mut self, tick: u64) -> Option<(NetInput, NetInput)> {
+        let inputs = if self.desynced || self.phase != NetPhase::Connected {
+            None
+        } else {
+            self.local_inputs
+                .get(&tick)
+                .copied()
+                .zip(self.remote_inputs.get(&tick).copied())
+        };
+        self.tick_ready = inputs.is_some();
+        inputs
+    }
+
+    /// Forgets inputs that are no longer needed, once `tick` has been simulated
+    pub fn finish_tick(&mut self, tick: u64) {
+        self.remote_inputs
+            .retain(|input_tick, _| *input_tick > tick);
+        // local inputs are kept until the peer says it has them
+        let acknowledged = self.peer_received_up_to;
+        self.local_inputs
+            .retain(|input_tick, _| Some(*input_tick) > acknowledged || *input_tick > tick);
+    }
+
+    /// Records this side's checksum for `tick` and sends it to be compared
+    pub fn record_checksum(&mut self, tick: u64, checksum: u64) {
+        self.local_checksums.insert(tick, checksum);
+        self.send(&NetMessage::Checksum {
+            round: self.round,
+            tick,
+            checksum,
+        });
+        self.check(tick);
+    }
+
+    // compares checksums once both sides have one for the tick
+    fn check(&mut self, tick: u64) {
+        let (Some(&local), Some(&remote)) = (
+            self.local_checksums.get(&tick),
+            self.remote_checksums.get(&tick),
+        ) else {
+            return;
+        };
+        // older ticks whose checksum from the other side got lost won't be compared now
+        self.local_checksums
+            .retain(|checksum_tick, _| *checksum_tick > tick);
+        self.remote_checksums
+            .retain(|checksum_tick, _| *checksum_tick > tick);
+        if local != remote && !self.desynced {
+            error!("Desynced from the other player on tick {tick}");
+            self.desynced = true;
+            self.events.push(NetEvent::Desync { tick });
+        }
+    }
+}
+
+impl Drop for NetSession {
+    fn drop(&mut self) {
+        // let the other player know straight away, rather than waiting for them to time out
+        if self.phase == NetPhase::Connected {
+            self.send(&NetMessage::Bye);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Rocks, logs and walls standing about the arena, and the shapes snakes and mice bump into.
+//! Where they stand is up to each level, see [`crate::levels`].
+
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObstacleKind {
+    Rock,
+    // only hurts, and knocks the snake back
+    Log,
+    Wall,
+}
+
+impl ObstacleKind {
+    /// How much health running into it takes.  `None` if it kills outright
+    pub fn damage(self) -> Option<f32> {
+        match self {
+            Self::Rock | Self::Wall => None,
+            Self::Log => Some(20.),
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Self::Rock => Color::rgb(0.5, 0.5, 0.55),
+            Self::Log => Color::rgb(0.45, 0.3, 0.15),
+            Self::Wall => Color::rgb(0.3, 0.3, 0.35),
+        }
+    }
+}
+
+impl fmt::Display for ObstacleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rock => write!(f, "rock"),
+            Self::Log => write!(f, "log"),
+            Self::Wall => write!(f, "wall"),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ObstacleShape {
+    Circle { radius: f32 },
+    // half its width and height, like the game field's boundary
+    Rect { half_size: Vec2 },
+}
+
+impl ObstacleShape {
+    /// The point on or in the shape, centred on `center`, that's closest to `point`
+    pub fn closest_point(self, center: Vec2, point: Vec2) -> Vec2 {
+        match self {
+            Self::Circle { radius } => {
+                let offset = point - center;
+                center + offset.clamp_length_max(radius)
+            }
+            Self::Rect { half_size } => point.clamp(center - half_size, center + half_size),
+        }
+    }
+
+    /// How far a circle of `radius` at `point` has to move to stop overlapping the shape, or
+    /// `None` if it doesn't
+    pub fn push_out(self, center: Vec2, point: Vec2, radius: f32) -> Option<Vec2> {
+        let closest = self.closest_point(center, point);
+        let away = point - closest;
+        let distance = away.length();
+        if distance >= radius {
+            return None;
+        }
+        if distance > 0. {
+            return Some(away / distance * (radius - distance));
+        }
+        // its middle is inside, so out the nearest side
+        match self {
+            Self::Circle { radius: size } => {
+                let out = (point - center).try_normalize().unwrap_or(Vec2::Y);
+                Some(center + out * (size + radius) - point)
+            }
+            Self::Rect { half_size } => {
+                let offset = point - center;
+                let depth = half_size - offset.abs() + radius;
+                let push = if depth.x < depth.y {
+                    Vec2::new(depth.x.copysign(offset.x), 0.)
+                } else {
+                    Vec2::new(0., depth.y.copysign(offset.y))
+                };
+                Some(push)
+            }
+        }
+    }
+
+    /// Whether a circle of `radius` at `point` would touch it
+    pub fn overlaps(self, center: Vec2, point: Vec2, radius: f32) -> bool {
+        self.closest_point(center, point).distance(point) < radius
+    }
+}
+
+/// One obstacle and where it stands
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ObstaclePlacement {
+    pub kind: ObstacleKind,
+    pub shape: ObstacleShape,
+    pub position: Vec2,
+}
@@ -0,0 +1,194 @@
+//! Recording of every run's per-tick input, and deterministic playback of it.
+//!
+//! A run is fully determined by its seed, the rivals in it, the level it's played on, the snake's
+//! controls on every tick and the upgrades bought between ticks, so that's all a replay stores.
+//! Controls are only written out on the ticks where they change.
+
+use std::{fs, io, path::Path};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{storage, Ability, Rivals, SnakeControls};
+
+// bump this whenever a change to the simulation would make old replays play back differently
+pub const REPLAY_VERSION: u32 = 9;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub rivals: Rivals,
+    // the id of the level it was played on
+    pub level: usize,
+    pub inputs: Vec<ReplayInput>,
+    pub purchases: Vec<ReplayPurchase>,
+    // how the run ended, so playback can be checked against it
+    pub ticks: u64,
+    pub score: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ReplayInput {
+    pub tick: u64,
+    pub direction: (f32, f32),
+    pub stick: (f32, f32),
+    // only ever used on the tick it's recorded against
+    pub ability: Option<Ability>,
+}
+
+impl ReplayInput {
+    fn new(tick: u64, controls: &SnakeControls) -> Self {
+        Self {
+            tick,
+            direction: controls.direction.into(),
+            stick: controls.stick.into(),
+            ability: controls.ability,
+        }
+    }
+
+    fn apply(&self, tick: u64, controls: &mut SnakeControls) {
+        controls.direction = self.direction.into();
+        controls.stick = self.stick.into();
+        controls.ability = self.ability.filter(|_| self.tick == tick);
+    }
+}
+
+// upgrades are bought while the game is paused, so they happen before the tick they're recorded
+// against
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReplayPurchase {
+    pub tick: u64,
+    pub upgrade_id: usize,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Format(ron::error::SpannedError),
+    Version(u32),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read replay: {e}"),
+            Self::Format(e) => write!(f, "replay is corrupt: {e}"),
+            Self::Version(version) => write!(
+                f,
+                "replay was recorded by an incompatible version of the game (v{version}, expected v{REPLAY_VERSION})"
+            ),
+        }
+    }
+}
+
+impl Replay {
+    pub fn new(seed: u64, rivals: Rivals, level: usize) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            rivals,
+            level,
+            inputs: Vec::new(),
+            purchases: Vec::new(),
+            ticks: 0,
+            score: 0,
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let replay = fs::read_to_string(path).map_err(ReplayError::Io)?;
+        let replay: Self = ron::from_str(&replay).map_err(ReplayError::Format)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::Version(replay.version));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let replay = ron::to_string(self).map_err(io::Error::other)?;
+        storage::write_atomic(path.as_ref(), replay.as_bytes())
+    }
+}
+
+/// Records the run currently being played.  Reset at the start of every run.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub replay: Replay,
+}
+
+impl ReplayRecorder {
+    pub fn record_controls(&mut self, tick: u64, controls: &SnakeControls) {
+        let input = ReplayInput::new(tick, controls);
+        let unchanged =
+            input.ability.is_none()
+                && self.replay.inputs.last().is_some_and(|last| {
+                    last.direction == input.direction && last.stick == input.stick
+                });
+        if !unchanged {
+            self.replay.inputs.push(input);
+        }
+    }
+
+    pub fn record_purchase(&mut self, tick: u64, upgrade_id: usize) {
+        self.replay
+            .purchases
+            .push(ReplayPurchase { tick, upgrade_id });
+    }
+}
+
+/// Present when a replay is being played back instead of taking the player's input
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    next_input: usize,
+    next_purchase: usize,
+    current: Option<ReplayInput>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_input: 0,
+            next_purchase: 0,
+            current: None,
+        }
+    }
+
+    pub fn rewind(&mut self) {
+        *self = Self::new(self.replay.clone());
+    }
+
+    /// Sets the controls to what they were on the given tick
+    pub fn apply_controls(&mut self, tick: u64, controls: &mut SnakeControls) {
+        while let Some(input) = self.replay.inputs.get(self.next_input) {
+            if input.tick > tick {
+                break;
+            }
+            self.current = Some(*input);
+            self.next_input += 1;
+        }
+        match self.current {
+            Some(input) => input.apply(tick, controls),
+            None => *controls = SnakeControls::default(),
+        }
+    }
+
+    /// The upgrades bought right before the given tick
+    pub fn take_purchases(&mut self, tick: u64) -> Vec<usize> {
+        let mut purchases = Vec::new();
+        while let Some(purchase) = self.replay.purchases.get(self.next_purchase) {
+            if purchase.tick > tick {
+                break;
+            }
+            purchases.push(purchase.upgrade_id);
+            self.next_purchase += 1;
+        }
+        purchases
+    }
+
+    pub fn finished(&self, tick: u64) -> bool {
+        tick >= self.replay.ticks
+    }
+}
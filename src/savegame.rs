@@ -0,0 +1,132 @@
+//! Saving an in-progress run when quitting, so it can be continued on the next launch.
+
+use std::{fs, io, path::PathBuf};
+
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    food::FoodKind, replay::Replay, storage, AbilityCooldowns, SnakeControls, StatusEffects,
+};
+
+const SAVEGAME_FILENAME: &str = "savegame.ron";
+
+// bump this whenever the format changes in a way old saves can't be read with
+pub const SAVEGAME_VERSION: u32 = 6;
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub snake: SavedSnake,
+    // the computer-controlled snakes still in the run, as difficult as the replay's rivals say
+    pub rivals: Vec<SavedSnake>,
+    pub score: usize,
+    pub coins: f32,
+    pub ticks: u64,
+    pub upgrades: Vec<usize>,
+    pub hunger_rate: f32,
+    pub food: Vec<SavedFood>,
+    pub coin_bags: Vec<SavedCoinBag>,
+    // the rng and the replay so far, so the rest of the run stays reproducible
+    pub seed: u64,
+    pub rng: ChaCha8Rng,
+    pub replay: Replay,
+}
+
+/// A snake and its tail
+#[derive(Serialize, Deserialize)]
+pub struct SavedSnake {
+    pub player: usize,
+    pub head: SavedBody,
+    pub health: f32,
+    pub max_health: f32,
+    pub speed: (f32, f32),
+    pub score: usize,
+    // rivals only change their minds every so often, so what they last decided has to carry over
+    pub controls: SnakeControls,
+    // how many ticks each of the head's abilities has left to cool down
+    pub cooldowns: AbilityCooldowns,
+    // and what it's eaten that's still wearing off
+    pub status_effects: StatusEffects,
+    // in order from the head to the tip of the tail
+    pub tail: Vec<SavedTailNode>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedBody {
+    pub translation: (f32, f32, f32),
+    pub velocity: (f32, f32, f32),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedFood {
+    pub translation: (f32, f32, f32),
+    pub velocity: (f32, f32, f32),
+    pub kind: FoodKind,
+    pub spawned: u64,
+    // which way it's facing, and how much it's noticed, both of which steer it
+    pub rotation: [f32; 4],
+    pub awareness: f32,
+    pub alerted: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedTailNode {
+    pub translation: (f32, f32, f32),
+    pub ignore_collision: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedCoinBag {
+    pub translation: (f32, f32, f32),
+    pub value: f32,
+}
+
+#[derive(Debug)]
+pub enum SaveGameError {
+    Io(io::Error),
+    Format(ron::error::SpannedError),
+    Version(u32),
+}
+
+impl std::fmt::Display for SaveGameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read saved game: {e}"),
+            Self::Format(e) => write!(f, "saved game is corrupt: {e}"),
+            Self::Version(version) => write!(
+                f,
+                "saved game is from an incompatible version of the game (v{version}, expected v{SAVEGAME_VERSION})"
+            ),
+        }
+    }
+}
+
+impl SaveGame {
+    fn path() -> PathBuf {
+        storage::data_dir().join(SAVEGAME_FILENAME)
+    }
+
+    pub fn exists() -> bool {
+        Self::path().exists()
+    }
+
+    pub fn load() -> Result<Self, SaveGameError> {
+        let save = fs::read_to_string(Self::path()).map_err(SaveGameError::Io)?;
+        let save: Self = ron::from_str(&save).map_err(SaveGameError::Format)?;
+        if save.version != SAVEGAME_VERSION {
+            return Err(SaveGameError::Version(save.version));
+        }
+        Ok(save)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let save = ron::to_string(self).map_err(io::Error::other)?;
+        storage::write_atomic(&Self::path(), save.as_bytes())
+    }
+
+    // a run can only be continued once, otherwise dying could just be undone by reloading
+    pub fn delete() -> io::Result<()> {
+        fs::remove_file(Self::path())
+    }
+}
@@ -0,0 +1,258 @@
+//! Player preferences, edited from the Settings menu and kept in the data directory between runs.
+
+use std::{fs, io, path::PathBuf};
+
+use bevy::{prelude::*, window::WindowMode as BevyWindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::{storage, RivalDifficulty, MAX_RIVALS};
+
+const SETTINGS_FILENAME: &str = "settings.ron";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowMode {
+    Windowed,
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+impl From<WindowMode> for BevyWindowMode {
+    fn from(mode: WindowMode) -> Self {
+        match mode {
+            WindowMode::Windowed => Self::Windowed,
+            WindowMode::BorderlessFullscreen => Self::BorderlessFullscreen,
+            WindowMode::Fullscreen => Self::Fullscreen,
+        }
+    }
+}
+
+/// Everything the player can change from the Settings menu.
+///
+/// Missing fields in the file fall back to their defaults, so adding a setting doesn't throw away
+/// the rest of an older file.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub window_mode: WindowMode,
+    pub vsync: bool,
+    // how fast the right stick pans the camera, in pixels per frame at full tilt
+    pub camera_sensitivity: f32,
+    // stick positions shorter than these are ignored, to stop sticks drifting
+    pub move_deadzone: f32,
+    pub camera_deadzone: f32,
+    // how far the triggers can zoom in and out, as camera projection scales
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    // whether the camera starts out following the snake
+    pub follow_camera: bool,
+    pub ui_scale: f32,
+    // the id of the level to play, from the next run on
+    pub level: usize,
+    // computer-controlled snakes to play against, from the next run on
+    pub rivals: usize,
+    pub rival_difficulty: RivalDifficulty,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowMode::BorderlessFullscreen,
+            vsync: true,
+            camera_sensitivity: 5.,
+            move_deadzone: 0.,
+            camera_deadzone: 0.1,
+            min_zoom: 0.3,
+            max_zoom: 2.,
+            follow_camera: false,
+            ui_scale: 1.,
+            level: 0,
+            rivals: 0,
+            rival_difficulty: RivalDifficulty::Normal,
+        }
+    }
+}
+
+/// One line of the Settings menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsEntry {
+    WindowMode,
+    Vsync,
+    CameraSensitivity,
+    MoveDeadzone,
+    CameraDeadzone,
+    MinZoom,
+    MaxZoom,
+    FollowCamera,
+    UiScale,
+    Level,
+    Rivals,
+    RivalDifficulty,
+    Controls,
+    Back,
+}
+
+impl SettingsEntry {
+    /// In the order they appear in the menu
+    pub const ALL: [Self; 14] = [
+        Self::WindowMode,
+        Self::Vsync,
+        Self::CameraSensitivity,
+        Self::MoveDeadzone,
+        Self::CameraDeadzone,
+        Self::MinZoom,
+        Self::MaxZoom,
+        Self::FollowCamera,
+        Self::UiScale,
+        Self::Level,
+        Self::Rivals,
+        Self::RivalDifficulty,
+        Self::Controls,
+        Self::Back,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WindowMode => "Window Mode",
+            Self::Vsync => "VSync",
+            Self::CameraSensitivity => "Camera Sensitivity",
+            Self::MoveDeadzone => "Movement Deadzone",
+            Self::CameraDeadzone => "Camera Deadzone",
+            Self::MinZoom => "Closest Zoom",
+            Self::MaxZoom => "Furthest Zoom",
+            Self::FollowCamera => "Follow Snake",
+            Self::UiScale => "UI Scale",
+            Self::Level => "Level",
+            Self::Rivals => "Rivals",
+            Self::RivalDifficulty => "Rival Difficulty",
+            Self::Controls => "Controls",
+            Self::Back => "Back",
+        }
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+impl Settings {
+    fn path() -> PathBuf {
+        storage::data_dir().join(SETTINGS_FILENAME)
+    }
+
+    /// Reads the settings file, falling back to the defaults if it's missing or unreadable
+    pub fn load() -> Self {
+        let path = Self::path();
+        let settings = match fs::read_to_string(&path) {
+            Ok(settings) => ron::from_str::<Self>(&settings).unwrap_or_else(|e| {
+                warn!("Using default settings, {} is corrupt: {e}", path.display());
+                Self::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                warn!(
+                    "Using default settings, could not read {}: {e}",
+                    path.display()
+                );
+                Self::default()
+            }
+        };
+        settings.sanitized()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let settings = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        storage::write_atomic(&Self::path(), settings.as_bytes())
+    }
+
+    // keeps hand-edited files from putting the game into a state the menu can't get it out of
+    fn sanitized(mut self) -> Self {
+        self.camera_sensitivity = self.camera_sensitivity.clamp(1., 20.);
+        self.move_deadzone = self.move_deadzone.clamp(0., 0.5);
+        self.camera_deadzone = self.camera_deadzone.clamp(0., 0.5);
+        self.min_zoom = self.min_zoom.clamp(0.1, 1.);
+        self.max_zoom = self.max_zoom.clamp(1., 4.);
+        self.ui_scale = self.ui_scale.clamp(0.5, 2.);
+        self.rivals = self.rivals.min(MAX_RIVALS);
+        self
+    }
+
+    /// What the menu shows next to an entry's label
+    pub fn value_text(&self, entry: SettingsEntry) -> String {
+        match entry {
+            SettingsEntry::WindowMode => match self.window_mode {
+                WindowMode::Windowed => "Windowed".into(),
+                WindowMode::BorderlessFullscreen => "Borderless".into(),
+                WindowMode::Fullscreen => "Fullscreen".into(),
+            },
+            SettingsEntry::Vsync => on_off(self.vsync).into(),
+            SettingsEntry::CameraSensitivity => format!("{:.0}", self.camera_sensitivity),
+            SettingsEntry::MoveDeadzone => format!("{:.2}", self.move_deadzone),
+            SettingsEntry::CameraDeadzone => format!("{:.2}", self.camera_deadzone),
+            SettingsEntry::MinZoom => format!("{:.1}x", self.min_zoom),
+            SettingsEntry::MaxZoom => format!("{:.1}x", self.max_zoom),
+            SettingsEntry::FollowCamera => on_off(self.follow_camera).into(),
+            SettingsEntry::UiScale => format!("{:.0}%", self.ui_scale * 100.),
+            // the menu shows the level's name instead, see `update_settings_menu`
+            SettingsEntry::Level => self.level.to_string(),
+            SettingsEntry::Rivals => self.rivals.to_string(),
+            SettingsEntry::RivalDifficulty => self.rival_difficulty.to_string(),
+            SettingsEntry::Controls | SettingsEntry::Back => String::new(),
+        }
+    }
+
+    /// Steps an entry's value up (`steps > 0`) or down.  Toggles and choices wrap around
+    pub fn adjust(&mut self, entry: SettingsEntry, steps: i32) {
+        let steps_f = steps as f32;
+        match entry {
+            SettingsEntry::WindowMode => {
+                let modes = [
+                    WindowMode::Windowed,
+                    WindowMode::BorderlessFullscreen,
+                    WindowMode::Fullscreen,
+                ];
+                let index = modes
+                    .iter()
+                    .position(|mode| *mode == self.window_mode)
+                    .unwrap_or(0);
+                let index = (index as i32 + steps).rem_euclid(modes.len() as i32);
+                self.window_mode = modes[index as usize];
+            }
+            SettingsEntry::Vsync => {
+                if steps % 2 != 0 {
+                    self.vsync = !self.vsync;
+                }
+            }
+            SettingsEntry::CameraSensitivity => self.camera_sensitivity += steps_f,
+            SettingsEntry::MoveDeadzone => self.move_deadzone += steps_f * 0.05,
+            SettingsEntry::CameraDeadzone => self.camera_deadzone += steps_f * 0.05,
+            SettingsEntry::MinZoom => self.min_zoom += steps_f * 0.1,
+            SettingsEntry::MaxZoom => self.max_zoom += steps_f * 0.25,
+            SettingsEntry::FollowCamera => {
+                if steps % 2 != 0 {
+                    self.follow_camera = !self.follow_camera;
+                }
+            }
+            SettingsEntry::UiScale => self.ui_scale += steps_f * 0.1,
+            SettingsEntry::Rivals => {
+                self.rivals = self.rivals.saturating_add_signed(steps as isize);
+            }
+            SettingsEntry::RivalDifficulty => {
+                let difficulties = RivalDifficulty::ALL;
+                let index = difficulties
+                    .iter()
+                    .position(|difficulty| *difficulty == self.rival_difficulty)
+                    .unwrap_or(0);
+                let index = (index as i32 + steps).rem_euclid(difficulties.len() as i32);
+                self.rival_difficulty = difficulties[index as usize];
+            }
+            // stepped through the level list by the menu, which knows what's in it
+            SettingsEntry::Level | SettingsEntry::Controls | SettingsEntry::Back => {}
+        }
+        *self = self.clone().sanitized();
+    }
+}
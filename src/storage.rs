@@ -0,0 +1,50 @@
+//! Where the game keeps files that should outlive a run, and how it writes them safely.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+const APP_DIRECTORY: &str = "snake";
+
+/// The per-user directory for the game's data, e.g `~/.local/share/snake` on Linux.
+///
+/// Falls back to the working directory if the platform doesn't tell us where that is.
+pub fn data_dir() -> PathBuf {
+    platform_data_dir()
+        .map(|dir| dir.join(APP_DIRECTORY))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
+
+/// Replaces the file at `path` with `contents`, so that it either has the old contents or the new
+/// ones, never half of each if the game is killed while writing.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::Game;
+
+pub fn advance_tick(mut game: ResMut<Game>) {
+    game.ticks += 1;
+}
@@ -0,0 +1,32 @@
+use bevy::{prelude::*, window::PresentMode};
+
+use crate::{levels::Levels, settings::Settings, CameraSettings, Rivals};
+
+pub fn apply_settings(
+    settings: Res<Settings>,
+    mut window: Query<&mut Window>,
+    mut ui_scale: ResMut<UiScale>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut rivals: ResMut<Rivals>,
+    mut levels: ResMut<Levels>,
+) {
+    // the first time this runs is after startup rather than when the window is created, so that
+    // fullscreen appears on the _current_ monitor for players with a multi-monitor setup.
+    // Without the delay, it appears on the primary monitor instead which is maybe not always what
+    // the player wanted/expected.
+    let mut window = window.single_mut();
+    window.mode = settings.window_mode.into();
+    window.present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    ui_scale.0 = settings.ui_scale as f64;
+    camera_settings.follow_snake = settings.follow_camera;
+    // picked up by the next restart, the rivals already on the field stay as they are
+    *rivals = Rivals {
+        count: settings.rivals,
+        difficulty: settings.rival_difficulty,
+    };
+    levels.selected = settings.level;
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::{
+    upgrades::UpgradeEffect, Rival, Snake, SnakeMaxHealth, SnakeSpeed, SnakeTailNode,
+    TailNodeCount, TailOf,
+};
+
+/// Applies one effect of an upgrade that has just been bought
+pub fn apply_upgrade_effect(
+    In(effect): In<UpgradeEffect>,
+    mut commands: Commands,
+    // rivals don't get any of it, it's only the players who bought it
+    mut snakes: Query<
+        (Entity, &mut SnakeSpeed, &mut SnakeMaxHealth),
+        (With<Snake>, Without<Rival>),
+    >,
+    tail_nodes: Query<(Entity, &TailOf, &TailNodeCount), With<SnakeTailNode>>,
+) {
+    match effect {
+        UpgradeEffect::Speed { analog, discrete } => {
+            for (_, mut speed, _) in &mut snakes {
+                speed.analog += analog;
+                speed.discrete += discrete;
+            }
+        }
+        UpgradeEffect::MaxHealth(amount) => {
+            for (_, _, mut max_health) in &mut snakes {
+                max_health.0 += amount;
+            }
+        }
+        // leaves the hunger rate alone, it's shared with the rivals and lowering it would
+        // help them just as much as the players who paid for it
+        UpgradeEffect::SplitTail(keep) => {
+            for (snake, ..) in &snakes {
+                let tail_node_count = tail_nodes
+                    .iter()
+                    .filter(|(_, TailOf(owner), _)| *owner == snake)
+                    .count();
+                let kept = (tail_node_count as f32 * keep) as usize;
+                for (tail_node, TailOf(owner), TailNodeCount(index)) in &tail_nodes {
+                    if *owner == snake && *index > kept {
+                        commands.entity(tail_node).despawn();
+                    }
+                }
+            }
+        }
+        // nothing to do until it's used, `use_abilities` checks what's been bought
+        UpgradeEffect::UnlockAbility(_) => {}
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+use crate::{systems::apply_upgrade_effect, Game, Upgrade};
+
+/// Spends the coins for an upgrade and applies it.  Returns false if it can't be afforded or has
+/// already been bought as many times as it can be.
+pub fn buy_upgrade(commands: &mut Commands, game: &mut Game, upgrade: &mut Upgrade) -> bool {
+    if upgrade.can_buy(game.coins) {
+        game.coins -= upgrade.price();
+        upgrade.level += 1;
+        game.upgrades.push(upgrade.id);
+        // effects are applied along with the rest of the commands, so a purchase made during a
+        // tick takes effect at the same point in it as it always has
+        let effects = upgrade.effects.clone();
+        commands.add(move |world: &mut World| {
+            for effect in effects {
+                world.run_system_once_with(effect, apply_upgrade_effect);
+            }
+        });
+        true
+    } else {
+        false
+    }
+}
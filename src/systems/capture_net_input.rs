@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::{net::NetSession, Snake, SnakeControls};
+
+/// Hands this frame's controls for the local snake to the online session, before
+/// `sync_net_inputs` replaces them with the delayed ones
+pub fn capture_net_input(
+    mut session: ResMut<NetSession>,
+    mut snakes: Query<(&Snake, &mut SnakeControls)>,
+) {
+    let local_player = session.local_player();
+    if let Some((_, mut controls)) = snakes
+        .iter_mut()
+        .find(|(snake, _)| snake.player == local_player)
+    {
+        session.set_latest_local(&controls);
+        // the session has it now, it'll come back round with the delayed inputs
+        controls.ability = None;
+    }
+}
@@ -0,0 +1,70 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    net::{NetSession, CHECKSUM_INTERVAL},
+    CoinBag, Food, Game, GameRng, Snake, TailNodeCount, TailOf, Velocity,
+};
+
+/// Once a tick has run, lets go of its inputs, and every so often sends a checksum of the game
+/// state to compare with the other player's
+pub fn checksum_net_state(
+    mut session: ResMut<NetSession>,
+    game: Res<Game>,
+    rng: Res<GameRng>,
+    snakes: Query<(&Snake, &Transform, &Velocity)>,
+    tail_nodes: Query<(&Transform, &TailOf, &TailNodeCount)>,
+    food: Query<(&Transform, &Food)>,
+    coin_bags: Query<&Transform, With<CoinBag>>,
+) {
+    // `advance_tick` has already moved on to the next one
+    let tick = game.ticks - 1;
+    session.finish_tick(tick);
+    if game.ticks % CHECKSUM_INTERVAL != 0 {
+        return;
+    }
+    // the exact bits matter here, two floats that print the same can still have drifted apart
+    let mut hasher = DefaultHasher::new();
+    let hash_vec3 = |hasher: &mut DefaultHasher, vec: Vec3| {
+        vec.to_array().map(f32::to_bits).hash(hasher);
+    };
+    game.score.hash(&mut hasher);
+    game.coins.to_bits().hash(&mut hasher);
+    rng.state().get_word_pos().hash(&mut hasher);
+    let mut heads: Vec<_> = snakes.iter().collect();
+    heads.sort_by_key(|(snake, _, _)| snake.player);
+    for (snake, head, Velocity(velocity)) in heads {
+        snake.player.hash(&mut hasher);
+        snake.score.hash(&mut hasher);
+        snake.health.to_bits().hash(&mut hasher);
+        hash_vec3(&mut hasher, head.translation);
+        hash_vec3(&mut hasher, *velocity);
+    }
+    // entity ids and query order can differ between the two games, e.g because of what's been
+    // drawn, so tail nodes go in by whose they are and where they are in the tail
+    let mut tail_nodes: Vec<_> = tail_nodes
+        .iter()
+        .filter_map(|(transform, TailOf(owner), TailNodeCount(index))| {
+            let (owner, _, _) = snakes.get(*owner).ok()?;
+            Some(((owner.player, *index), transform.translation))
+        })
+        .collect();
+    tail_nodes.sort_by_key(|(key, _)| *key);
+    for (_, translation) in tail_nodes {
+        hash_vec3(&mut hasher, translation);
+    }
+    let mut food: Vec<_> = food.iter().collect();
+    food.sort_by_key(|(_, food)| food.spawned);
+    for (transform, food) in food {
+        food.kind.hash(&mut hasher);
+        hash_vec3(&mut hasher, transform.translation);
+    }
+    for transform in &coin_bags {
+        hash_vec3(&mut hasher, transform.translation);
+    }
+    session.record_checksum(tick, hasher.finish());
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState},
+    AppState,
+};
+
+pub fn close_upgrades_menu(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut actions: ResMut<ActionState>,
+) {
+    // back out to the pause menu
+    if actions.clear_just_pressed(Action::Back) {
+        // escape is bound to both by default, and should only go back one screen
+        actions.clear_just_pressed(Action::Pause);
+        next_state.set(AppState::Paused);
+    }
+    // pause always goes straight back to the game, same as from the pause menu
+    if actions.clear_just_pressed(Action::Pause) {
+        next_state.set(AppState::Playing);
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::CoinBag;
+
+const COIN_LEAK_PER_SECOND: f32 = 0.6;
+
+pub fn coinbag_leak(
+    mut commands: Commands,
+    mut coins: Query<(&mut CoinBag, Entity)>,
+    time: Res<Time>,
+) {
+    for (mut coins, coins_entity) in &mut coins {
+        coins.value -= COIN_LEAK_PER_SECOND * time.delta_seconds();
+        if coins.value <= 0. {
+            commands.entity(coins_entity).despawn();
+        }
+    }
+}
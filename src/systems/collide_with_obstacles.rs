@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::{
+    DeathCause, Obstacle, Snake, SnakeDiedEvent, StatusEffect, StatusEffects, Velocity,
+    SNAKE_HEAD_RADIUS,
+};
+
+// how much of its speed a snake keeps when it bounces off something that doesn't kill it
+const BOUNCE: f32 = 0.5;
+
+/// Stops snakes going through obstacles.  Rocks and walls kill them, logs knock them back and
+/// take a bite out of their health.
+///
+/// Runs straight after `move_snake`, so a snake is never drawn inside one.
+pub fn collide_with_obstacles(
+    mut snakes: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &mut Snake,
+        &StatusEffects,
+    )>,
+    obstacles: Query<(&Transform, &Obstacle), Without<Snake>>,
+    mut ev_snake_died: EventWriter<SnakeDiedEvent>,
+) {
+    for (entity, mut head_transform, mut head_velocity, mut snake, status_effects) in &mut snakes {
+        let Velocity(ref mut head_velocity) = *head_velocity;
+        let invulnerable = status_effects.has(StatusEffect::Invulnerable);
+        for (obstacle_transform, obstacle) in &obstacles {
+            let Some(push) = obstacle.shape.push_out(
+                obstacle_transform.translation.truncate(),
+                head_transform.translation.truncate(),
+                SNAKE_HEAD_RADIUS,
+            ) else {
+                continue;
+            };
+            head_transform.translation += push.extend(0.);
+            let normal = push.normalize_or_zero().extend(0.);
+            let into = head_velocity.dot(normal);
+            // only hurts when it's heading into it, not while it's sliding off
+            if into >= 0. {
+                continue;
+            }
+            *head_velocity -= normal * into * (1. + BOUNCE);
+            if invulnerable {
+                continue;
+            }
+            let died = match obstacle.kind.damage() {
+                Some(damage) => {
+                    snake.health -= damage;
+                    snake.health <= 0.
+                }
+                None => true,
+            };
+            if died {
+                ev_snake_died.send(SnakeDiedEvent {
+                    snake: entity,
+                    cause: DeathCause::HitObstacle(obstacle.kind),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::{
+    DeathCause, Snake, SnakeDiedEvent, SnakeTailNode, StatusEffect, StatusEffects, TailOf,
+    SELF_COLLISION_MIN_TAIL, SNAKE_HEAD_RADIUS,
+};
+
+pub fn collide_with_tails(
+    snakes: Query<(Entity, &Transform, &Snake, &StatusEffects), Without<SnakeTailNode>>,
+    tail_nodes: Query<(&Transform, &SnakeTailNode, &TailOf)>,
+    mut ev_snake_died: EventWriter<SnakeDiedEvent>,
+) {
+    for (snake_entity, head, snake, status_effects) in &snakes {
+        if status_effects.has(StatusEffect::Invulnerable) {
+            continue;
+        }
+        let own_tail_length = tail_nodes
+            .iter()
+            .filter(|(_, _, TailOf(owner))| *owner == snake_entity)
+            .count();
+        for (tail_node, SnakeTailNode(ignore_collision), TailOf(owner)) in &tail_nodes {
+            let own_tail = *owner == snake_entity;
+            if own_tail && (*ignore_collision || own_tail_length < SELF_COLLISION_MIN_TAIL) {
+                continue;
+            }
+            if tail_node.translation.distance(head.translation) < SNAKE_HEAD_RADIUS * 2. {
+                let cause = if own_tail {
+                    DeathCause::HitOwnTail
+                } else {
+                    let player = snakes
+                        .get(*owner)
+                        .map_or(snake.player, |(_, _, other, _)| other.player);
+                    DeathCause::HitOtherSnake(player)
+                };
+                ev_snake_died.send(SnakeDiedEvent {
+                    snake: snake_entity,
+                    cause,
+                });
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    tail_node_bundle, CoinBag, DeathCause, Food, Game, GameRng, HungerRate, Rival, Snake,
+    SnakeDiedEvent, SnakeMaxHealth, SnakeTailNode, StatusEffects, TailNodeCount, TailOf,
+    FOOD_RADIUS, SNAKE_HEAD_RADIUS, TAIL_NODE_GAP,
+};
+
+pub fn consume_items(
+    mut commands: Commands,
+    food: Query<(&Transform, Entity, &Food)>,
+    coins: Query<(&Transform, Entity, &CoinBag), With<CoinBag>>,
+    mut snakes: Query<(
+        Entity,
+        &Transform,
+        &mut Snake,
+        &SnakeMaxHealth,
+        &mut StatusEffects,
+        Has<Rival>,
+    )>,
+    tail_nodes: Query<(&Transform, &TailOf, &TailNodeCount), With<SnakeTailNode>>,
+    mut game: ResMut<Game>,
+    mut hunger_rate: ResMut<HungerRate>,
+    mut rng: ResMut<GameRng>,
+    mut ev_snake_died: EventWriter<SnakeDiedEvent>,
+) {
+    // snakes are checked in player order, so whoever is first in the list wins a tie
+    let mut snakes: Vec<_> = snakes.iter_mut().collect();
+    snakes.sort_by_key(|(_, _, snake, ..)| snake.player);
+    // consume_food, oldest first, since query order can differ between two games of the same run.
+    // A snake swallows one at a time, anything else it's touching has to wait for the next tick
+    let mut food: Vec<_> = food.iter().collect();
+    food.sort_by_key(|(_, _, food)| food.spawned);
+    let mut fed: Vec<Entity> = Vec::new();
+    for (food, food_entity, Food { kind, .. }) in food {
+        let stats = kind.stats();
+        let eater = snakes.iter_mut().find(|(snake_entity, head, ..)| {
+            !fed.contains(snake_entity)
+                && food.translation.distance(head.translation) < (SNAKE_HEAD_RADIUS + stats.radius)
+        });
+        if let Some((snake_entity, head, snake, max_health, status_effects, rival)) = eater {
+            // food consumed
+            fed.push(*snake_entity);
+            commands.entity(food_entity).despawn();
+            snake.score += stats.score;
+            snake.health += stats.health;
+            snake.health = snake.health.clamp(0., max_health.0);
+            if let Some((effect, seconds)) = stats.effect {
+                status_effects.add(effect, seconds);
+            }
+            if snake.health <= 0. {
+                ev_snake_died.send(SnakeDiedEvent {
+                    snake: *snake_entity,
+                    cause: DeathCause::Poisoned,
+                });
+            }
+            // rivals only take food away from the players, they don't count towards the run
+            if !*rival {
+                game.score += stats.score;
+                game.coins += stats.coins;
+                if stats.score > 0 {
+                    hunger_rate.0 += 0.5; // as the snake eats, it gets hungrier faster
+                }
+            }
+            // only food that's worth something grows the tail
+            if stats.score == 0 {
+                continue;
+            }
+
+            let mut tail_nodes_vec: Vec<_> = tail_nodes
+                .iter()
+                .filter(|(_, TailOf(owner), _)| owner == snake_entity)
+                .collect();
+            tail_nodes_vec.sort_by_key(|(_, _, TailNodeCount(index))| *index);
+            let tail_nodes_count = tail_nodes_vec.len();
+            let last_tail_node = tail_nodes_vec.last();
+            let offset_origin = match last_tail_node {
+                Some((last_tail_node, _, _)) => last_tail_node.translation,
+                None => head.translation,
+            };
+            let angle_to_offset = if tail_nodes_count >= 2 {
+                let penultimate_tail_node = tail_nodes_vec.get(tail_nodes_count - 2).unwrap();
+                offset_origin.angle_between(penultimate_tail_node.0.translation)
+            } else {
+                rng.gen()
+            };
+            let offset_vector = Vec3::new(
+                TAIL_NODE_GAP * angle_to_offset.cos(),
+                TAIL_NODE_GAP * angle_to_offset.sin(),
+                0.0,
+            );
+            let ignore_collision = tail_nodes_count < 1;
+            commands.spawn(tail_node_bundle(
+                *snake_entity,
+                offset_origin + offset_vector,
+                ignore_collision,
+                tail_nodes_count,
+            ));
+        }
+    }
+    // consume_coins, which go into the pot everyone shops from
+    if let Ok((coins_transform, coins_entity, coins)) = coins.get_single() {
+        let collector = snakes.iter().find(|(_, head, ..)| {
+            coins_transform.translation.distance(head.translation)
+                < (SNAKE_HEAD_RADIUS + FOOD_RADIUS)
+        });
+        if let Some((.., rival)) = collector {
+            commands.entity(coins_entity).despawn();
+            // a rival getting there first just means nobody gets them
+            if !*rival {
+                game.coins += coins.value;
+            }
+            // game.coins += (rand::thread_rng().gen_range(8.0..12.0f32) * 100.0).round() / 100.0;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState, Binding, InputBindings},
+    AppState, ControlsEntry, ControlsMenuRow, ControlsMenuState,
+};
+
+pub fn controls_menu_navigation(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut actions: ResMut<ActionState>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut menu_state: ResMut<ControlsMenuState>,
+    mut bindings: ResMut<InputBindings>,
+    interactions: Query<(&Interaction, &ControlsMenuRow), Changed<Interaction>>,
+) {
+    let entries: Vec<_> = ControlsEntry::all().collect();
+    let selected = entries[menu_state.selected];
+
+    if menu_state.waiting {
+        // take the next key or button as the new binding, straight from the raw input so that
+        // keys which are already bound to something can be picked too
+        if keys.just_pressed(KeyCode::Escape) {
+            menu_state.waiting = false;
+        } else if let (ControlsEntry::Action(action), Some(binding)) = (
+            selected,
+            keys.get_just_pressed()
+                .next()
+                .map(|key| Binding::Key(*key))
+                .or_else(|| {
+                    buttons
+                        .get_just_pressed()
+                        .next()
+                        .map(|button| Binding::Gamepad(button.button_type))
+                }),
+        ) {
+            bindings.rebind(action, binding);
+            menu_state.waiting = false;
+        }
+        // nothing else gets to react to the key that was just bound
+        for action in Action::ALL {
+            actions.clear_just_pressed(action);
+        }
+        return;
+    }
+
+    let mut confirmed = false;
+    for (interaction, ControlsMenuRow(entry)) in &interactions {
+        let index = entries
+            .iter()
+            .position(|candidate| candidate == entry)
+            .unwrap_or(0);
+        match *interaction {
+            Interaction::Pressed => {
+                menu_state.selected = index;
+                confirmed = true;
+            }
+            Interaction::Hovered => {
+                menu_state.selected = index;
+            }
+            Interaction::None => {}
+        }
+    }
+    if actions.clear_just_pressed(Action::MenuUp) {
+        menu_state.selected = (menu_state.selected + entries.len() - 1) % entries.len();
+    }
+    if actions.clear_just_pressed(Action::MenuDown) {
+        menu_state.selected = (menu_state.selected + 1) % entries.len();
+    }
+    if actions.clear_just_pressed(Action::Confirm) {
+        confirmed = true;
+    }
+    if actions.clear_just_pressed(Action::Back) {
+        next_state.set(AppState::Settings);
+    }
+    if confirmed {
+        match entries[menu_state.selected] {
+            ControlsEntry::Action(_) => menu_state.waiting = true,
+            ControlsEntry::ResetDefaults => *bindings = InputBindings::default(),
+            ControlsEntry::Back => next_state.set(AppState::Settings),
+        }
+    }
+}
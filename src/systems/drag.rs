@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::Velocity;
+
+// fraction of its velocity that something keeps after coasting for one second
+const DRAG_COEFFICIENT: f32 = 0.3;
+const MAX_VELOCITY: f32 = 10.0;
+
+pub fn drag(mut velocities: Query<&mut Velocity>, time: Res<Time>) {
+    let drag = DRAG_COEFFICIENT.powf(time.delta_seconds());
+    for mut velocity in &mut velocities {
+        let Velocity(ref mut velocity) = *velocity;
+        *velocity *= drag;
+        if velocity.length() < 1.0 {
+            *velocity = Vec3::ZERO;
+        }
+        velocity.clamp_length(0.0, MAX_VELOCITY);
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::{
+    replay::{ReplayPlayback, ReplayRecorder},
+    Game,
+};
+
+pub fn finish_replay(
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<Res<ReplayPlayback>>,
+    game: Res<Game>,
+) {
+    recorder.replay.ticks = game.ticks;
+    recorder.replay.score = game.score;
+    if let Some(playback) = playback {
+        let expected = &playback.replay;
+        if expected.ticks == game.ticks && expected.score == game.score {
+            info!(
+                "Replay verified: score {} after {} ticks",
+                game.score, game.ticks
+            );
+        } else {
+            warn!(
+                "Replay desynced: expected score {} after {} ticks, got score {} after {} ticks",
+                expected.score, expected.ticks, game.score, game.ticks
+            );
+        }
+    }
+}
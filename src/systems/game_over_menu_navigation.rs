@@ -0,0 +1,44 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    actions::Action, actions::ActionState, GameOverButtonHighlightedEvent,
+    GameOverMenuSelectedButton, RestartEvent,
+};
+
+pub fn game_over_menu_navigation(
+    selected_button: Res<GameOverMenuSelectedButton>,
+    mut actions: ResMut<ActionState>,
+    mut ev_button_highlighted: EventWriter<GameOverButtonHighlightedEvent>,
+    mut ev_restart: EventWriter<RestartEvent>,
+    mut ev_quit: EventWriter<AppExit>,
+) {
+    let next_button = match *selected_button {
+        GameOverMenuSelectedButton::None => GameOverMenuSelectedButton::Quit,
+        GameOverMenuSelectedButton::Quit => GameOverMenuSelectedButton::Restart,
+        GameOverMenuSelectedButton::Restart => GameOverMenuSelectedButton::Quit,
+    };
+    let prev_button = match *selected_button {
+        GameOverMenuSelectedButton::None => GameOverMenuSelectedButton::Restart,
+        GameOverMenuSelectedButton::Quit => GameOverMenuSelectedButton::Restart,
+        GameOverMenuSelectedButton::Restart => GameOverMenuSelectedButton::Quit,
+    };
+    if actions.clear_just_pressed(Action::MenuRight) {
+        ev_button_highlighted.send(GameOverButtonHighlightedEvent(next_button));
+    }
+    if actions.clear_just_pressed(Action::MenuLeft) {
+        ev_button_highlighted.send(GameOverButtonHighlightedEvent(prev_button));
+    }
+    if actions.clear_just_pressed(Action::Confirm) {
+        match *selected_button {
+            GameOverMenuSelectedButton::Quit => {
+                ev_quit.send_default();
+            }
+            GameOverMenuSelectedButton::Restart => {
+                ev_restart.send_default();
+            }
+            GameOverMenuSelectedButton::None => {
+                // no-op
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::{
+    GameOverButtonHighlightedEvent, GameOverMenuSelectedButton, PauseMenu, QuitButton,
+    RestartButton,
+};
+
+pub fn game_over_menu_selected_button_update(
+    mut restart_button: Query<&mut Style, (With<RestartButton>, Without<QuitButton>)>,
+    mut quit_button: Query<
+        &mut Style,
+        (With<QuitButton>, Without<RestartButton>, Without<PauseMenu>),
+    >,
+    mut highlighted_button: ResMut<GameOverMenuSelectedButton>,
+    mut ev_button_highlighted: EventReader<GameOverButtonHighlightedEvent>,
+) {
+    let mut restart_button = restart_button.single_mut();
+    let mut quit_button = quit_button.single_mut();
+    for selected_button in ev_button_highlighted.read() {
+        match selected_button {
+            GameOverButtonHighlightedEvent(GameOverMenuSelectedButton::None) => {
+                restart_button.border = UiRect::default();
+                restart_button.margin.bottom = Val::Px(0.);
+                quit_button.border = UiRect::default();
+                quit_button.margin.bottom = Val::Px(0.);
+            }
+            GameOverButtonHighlightedEvent(GameOverMenuSelectedButton::Restart) => {
+                restart_button.border = UiRect::bottom(Val::Px(2.));
+                restart_button.margin.bottom = Val::Px(-2.);
+                quit_button.border = UiRect::default();
+                quit_button.margin.bottom = Val::Px(0.);
+            }
+            GameOverButtonHighlightedEvent(GameOverMenuSelectedButton::Quit) => {
+                restart_button.border = UiRect::default();
+                restart_button.margin.bottom = Val::Px(0.);
+                quit_button.border = UiRect::bottom(Val::Px(2.0));
+                quit_button.margin.bottom = Val::Px(-2.);
+            }
+        }
+        *highlighted_button = selected_button.0;
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{GameOverButtonHighlightedEvent, GameOverMenuSelectedButton, QuitButton};
+
+pub fn game_over_on_quit_clicked(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut ev_select_button: EventWriter<GameOverButtonHighlightedEvent>,
+) {
+    for interaction in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                app_exit_events.send_default();
+            }
+            Interaction::Hovered => {
+                ev_select_button.send(GameOverButtonHighlightedEvent(
+                    GameOverMenuSelectedButton::Quit,
+                ));
+            }
+            Interaction::None => {
+                ev_select_button.send(GameOverButtonHighlightedEvent(
+                    GameOverMenuSelectedButton::None,
+                ));
+            }
+        }
+    }
+}
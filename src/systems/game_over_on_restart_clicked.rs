@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::{
+    GameOverButtonHighlightedEvent, GameOverMenuSelectedButton, RestartButton, RestartEvent,
+};
+
+pub fn game_over_on_restart_clicked(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<RestartButton>)>,
+    mut ev_select_button: EventWriter<GameOverButtonHighlightedEvent>,
+    mut ev_restart: EventWriter<RestartEvent>,
+) {
+    for interaction in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_restart.send_default();
+            }
+            Interaction::Hovered => {
+                ev_select_button.send(GameOverButtonHighlightedEvent(
+                    GameOverMenuSelectedButton::Restart,
+                ));
+            }
+            Interaction::None => {
+                ev_select_button.send(GameOverButtonHighlightedEvent(
+                    GameOverMenuSelectedButton::None,
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::{
+    levels::Level, DeathCause, Game, GameMode, GameOverEvent, PlayerResult, Rival, RoundResults,
+    Snake, SnakeDiedEvent, TailOf,
+};
+
+/// Takes snakes that died this tick off the field, and ends the round once the mode says so, or
+/// once the level's goal has been reached
+pub fn handle_snake_deaths(
+    mut commands: Commands,
+    mut ev_snake_died: EventReader<SnakeDiedEvent>,
+    mut ev_game_over: EventWriter<GameOverEvent>,
+    snakes: Query<(Entity, &Snake, Has<Rival>)>,
+    tail_nodes: Query<(Entity, &TailOf)>,
+    mut results: ResMut<RoundResults>,
+    mode: Res<GameMode>,
+    game: Res<Game>,
+    level: Res<Level>,
+) {
+    // a snake can die of several things in the same tick, only the first one counts
+    let mut died: Vec<(Entity, DeathCause)> = Vec::new();
+    for ev in ev_snake_died.read() {
+        if !died.iter().any(|(snake, _)| *snake == ev.snake) {
+            died.push((ev.snake, ev.cause));
+        }
+    }
+    let level_complete = level.goal.reached(&game);
+    if died.is_empty() && !level_complete {
+        return;
+    }
+    let tail_length = |snake: Entity| {
+        tail_nodes
+            .iter()
+            .filter(|(_, TailOf(owner))| *owner == snake)
+            .count()
+    };
+    for (snake_entity, cause) in &died {
+        let Ok((_, snake, _)) = snakes.get(*snake_entity) else {
+            continue;
+        };
+        results.results.push(PlayerResult {
+            player: snake.player,
+            score: snake.score,
+            tail_length: tail_length(*snake_entity),
+            ticks: game.ticks,
+            cause: Some(*cause),
+        });
+    }
+    let survivors: Vec<_> = snakes
+        .iter()
+        .filter(|(entity, _, _)| !died.iter().any(|(snake, _)| snake == entity))
+        .collect();
+    // there's nothing left to watch once every player is out, however many rivals are left
+    let players_left = survivors.iter().filter(|(_, _, rival)| !rival).count();
+    let round_over = level_complete
+        || match *mode {
+            GameMode::Versus if snakes.iter().count() > 1 => {
+                survivors.len() <= 1 || players_left == 0
+            }
+            _ => players_left == 0,
+        };
+    if round_over {
+        results.level_complete = level_complete;
+        // whoever is left standing placed better than everyone who died
+        for (snake_entity, snake, _) in survivors {
+            results.results.push(PlayerResult {
+                player: snake.player,
+                score: snake.score,
+                tail_length: tail_length(snake_entity),
+                ticks: game.ticks,
+                cause: None,
+            });
+        }
+        ev_game_over.send_default();
+    } else {
+        for (snake_entity, _) in &died {
+            commands.entity(*snake_entity).despawn_recursive();
+            for (tail_node, TailOf(owner)) in &tail_nodes {
+                if owner == snake_entity {
+                    commands.entity(tail_node).despawn();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::PhysicalTranslation;
+
+// smooths movement out between simulation ticks, for refresh rates that don't match SIMULATION_HZ
+pub fn interpolate_translation(
+    mut physical: Query<(&mut Transform, &PhysicalTranslation)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let alpha = fixed_time.overstep_percentage();
+    for (mut transform, physical) in &mut physical {
+        transform.translation = physical.previous.lerp(physical.current, alpha);
+    }
+}
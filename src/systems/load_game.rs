@@ -0,0 +1,141 @@
+use std::iter;
+
+use bevy::prelude::*;
+
+use crate::{
+    coin_bag_bundle, food_bundle,
+    levels::{Level, Levels},
+    replay::{ReplayPlayback, ReplayRecorder},
+    savegame::SaveGame,
+    snake_bundle, tail_node_bundle, AppState, Awareness, CoinBag, Food, Game, GameMode, GameRng,
+    HungerRate, LoadGameEvent, LocalPlayers, Rival, Rivals, RoundResults, Snake, SnakeMaxHealth,
+    SnakeSpeed, SnakeTailNode, Upgrades,
+};
+
+pub fn load_game(
+    mut ev_load_game: EventReader<LoadGameEvent>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut game: ResMut<Game>,
+    mut rng: ResMut<GameRng>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut hunger_rate: ResMut<HungerRate>,
+    levels: Res<Levels>,
+    mut level: ResMut<Level>,
+    mut upgrades: ResMut<Upgrades>,
+    mut players: ResMut<LocalPlayers>,
+    mut rivals: ResMut<Rivals>,
+    mut mode: ResMut<GameMode>,
+    mut results: ResMut<RoundResults>,
+    despawn: Query<Entity, Or<(With<Snake>, With<SnakeTailNode>, With<Food>, With<CoinBag>)>>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    if ev_load_game.is_empty() {
+        return;
+    }
+    ev_load_game.clear();
+    // a replay drives the run itself, so there's nothing to continue
+    if playback.is_some() {
+        return;
+    }
+
+    let save = match SaveGame::load() {
+        Ok(save) => save,
+        Err(e) => {
+            warn!("Could not continue saved game: {e}");
+            return;
+        }
+    };
+    // the run can't carry on the same way anywhere else
+    let Some(saved_level) = levels.by_id(save.replay.level) else {
+        warn!(
+            "Could not continue saved game: there's no level {} any more",
+            save.replay.level
+        );
+        return;
+    };
+    if let Err(e) = SaveGame::delete() {
+        warn!("Could not remove saved game after loading it: {e}");
+    }
+
+    for entity in &despawn {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    *level = saved_level.clone();
+    // saved games are always single player
+    *players = LocalPlayers::default();
+    *rivals = save.replay.rivals;
+    *mode = GameMode::Classic;
+    *results = RoundResults::default();
+    let difficulty = rivals.difficulty;
+    let rival_snakes = save.rivals.into_iter().map(|saved| (saved, true));
+    for (saved, rival) in iter::once((save.snake, false)).chain(rival_snakes) {
+        let snake = commands
+            .spawn(snake_bundle(
+                Snake {
+                    health: saved.health,
+                    player: saved.player,
+                    score: saved.score,
+                },
+                saved.head.translation.into(),
+                saved.head.velocity.into(),
+                saved.cooldowns,
+                saved.status_effects,
+            ))
+            .insert((
+                saved.controls,
+                SnakeSpeed {
+                    analog: saved.speed.0,
+                    discrete: saved.speed.1,
+                },
+                SnakeMaxHealth(saved.max_health),
+            ))
+            .id();
+        if rival {
+            commands.entity(snake).insert(Rival(difficulty));
+        }
+        for (index, node) in saved.tail.into_iter().enumerate() {
+            commands.spawn(tail_node_bundle(
+                snake,
+                node.translation.into(),
+                node.ignore_collision,
+                index,
+            ));
+        }
+    }
+    for food in save.food {
+        commands.spawn(food_bundle(
+            food.kind,
+            food.spawned,
+            food.translation.into(),
+            food.velocity.into(),
+            Quat::from_array(food.rotation),
+            Awareness {
+                level: food.awareness,
+                alerted: food.alerted,
+            },
+        ));
+    }
+    for coin_bag in save.coin_bags {
+        commands.spawn(coin_bag_bundle(coin_bag.translation.into(), coin_bag.value));
+    }
+
+    *game = Game {
+        score: save.score,
+        coins: save.coins,
+        ticks: save.ticks,
+        upgrades: save.upgrades,
+    };
+    hunger_rate.0 = save.hunger_rate;
+    upgrades.set_levels(&game.upgrades);
+    rng.resume(save.seed, save.rng);
+    recorder.replay = save.replay;
+
+    info!(
+        "Continuing saved game with seed {} after {} ticks",
+        rng.seed(),
+        game.ticks
+    );
+    next_state.set(AppState::Playing);
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::levels::{LevelCatalogue, LevelCatalogueHandle};
+
+/// Starts loading the levels file, see `reload_levels` for when it's done
+pub fn load_levels(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelCatalogueHandle(
+        asset_server.load(LevelCatalogue::PATH),
+    ));
+}
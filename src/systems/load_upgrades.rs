@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::upgrades::{UpgradeCatalogue, UpgradeCatalogueHandle};
+
+/// Starts loading the upgrade catalogue file, see `reload_upgrades` for when it's done
+pub fn load_upgrades(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(UpgradeCatalogueHandle(
+        asset_server.load(UpgradeCatalogue::PATH),
+    ));
+}
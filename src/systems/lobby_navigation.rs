@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState, InputBindings, InputDevice, RawInput},
+    AppState, GameMode, LocalPlayers, RestartEvent, MAX_LOCAL_PLAYERS,
+};
+
+pub fn lobby_navigation(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut actions: ResMut<ActionState>,
+    bindings: Res<InputBindings>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    mut players: ResMut<LocalPlayers>,
+    mut mode: ResMut<GameMode>,
+    mut ev_restart: EventWriter<RestartEvent>,
+) {
+    let input = RawInput {
+        keys: &keys,
+        gamepads: &gamepads,
+        buttons: &buttons,
+        button_axes: &button_axes,
+    };
+    let devices =
+        std::iter::once(InputDevice::Keyboard).chain(gamepads.iter().map(InputDevice::Gamepad));
+    for device in devices {
+        let joined = players.devices.contains(&device);
+        if bindings.just_pressed_on(Action::Confirm, device, &input)
+            && !joined
+            && players.devices.len() < MAX_LOCAL_PLAYERS
+        {
+            players.devices.push(device);
+        }
+        if bindings.just_pressed_on(Action::Back, device, &input) {
+            if joined {
+                players.devices.retain(|player| *player != device);
+            } else {
+                next_state.set(AppState::MainMenu);
+            }
+        }
+    }
+    if actions.clear_just_pressed(Action::MenuLeft) | actions.clear_just_pressed(Action::MenuRight)
+    {
+        *mode = match *mode {
+            GameMode::Versus => GameMode::Coop,
+            _ => GameMode::Versus,
+        };
+    }
+    // escape is bound to both back and pause by default, and here it should only mean back
+    let back = actions.clear_just_pressed(Action::Back);
+    if actions.clear_just_pressed(Action::Pause) && !back && !players.devices.is_empty() {
+        ev_restart.send_default();
+    }
+}
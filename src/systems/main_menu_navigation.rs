@@ -0,0 +1,55 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    actions::{Action, ActionState},
+    AppState, LoadGameEvent, MainMenuButton, MainMenuSelectedButton, RestartEvent,
+};
+
+pub fn main_menu_navigation(
+    mut ev_restart: EventWriter<RestartEvent>,
+    mut ev_load_game: EventWriter<LoadGameEvent>,
+    mut actions: ResMut<ActionState>,
+    mut selected_button: ResMut<MainMenuSelectedButton>,
+    mut ev_quit: EventWriter<AppExit>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut menu_buttons: Query<(&MainMenuButton, &mut Style)>,
+) {
+    for (button, mut style) in &mut menu_buttons {
+        if button.0 == *selected_button {
+            style.border.bottom = Val::Px(2.);
+            style.margin.bottom = Val::Px(-2.);
+        } else {
+            style.border.bottom = Val::ZERO;
+            style.margin.bottom = Val::ZERO;
+        }
+    }
+    // Continue is only there when there's a saved game
+    let mut available: Vec<_> = menu_buttons.iter().map(|(button, _)| button.0).collect();
+    available.sort_by_key(|button| *button as u8);
+
+    if actions.clear_just_pressed(Action::MenuUp) {
+        selected_button.step(&available, false);
+    }
+    if actions.clear_just_pressed(Action::MenuDown) {
+        selected_button.step(&available, true);
+    }
+    // start on a gamepad confirms too, since it's what most players reach for on a title screen
+    let confirmed =
+        actions.clear_just_pressed(Action::Confirm) | actions.clear_just_pressed(Action::Pause);
+    if confirmed {
+        match *selected_button {
+            MainMenuSelectedButton::Continue => {
+                ev_load_game.send_default();
+            }
+            MainMenuSelectedButton::Play => {
+                ev_restart.send_default();
+            }
+            MainMenuSelectedButton::Multiplayer => {
+                next_state.set(AppState::Lobby);
+            }
+            MainMenuSelectedButton::Quit => {
+                ev_quit.send_default();
+            }
+        }
+    }
+}
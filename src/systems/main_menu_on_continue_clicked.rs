@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::{ContinueButton, LoadGameEvent, MainMenuSelectedButton};
+
+pub fn main_menu_on_continue_clicked(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ContinueButton>)>,
+    mut selected_button: ResMut<MainMenuSelectedButton>,
+    mut ev_load_game: EventWriter<LoadGameEvent>,
+) {
+    for interaction in &interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_load_game.send_default();
+            }
+            Interaction::Hovered => {
+                *selected_button = MainMenuSelectedButton::Continue;
+            }
+            Interaction::None => {}
+        }
+    }
+}
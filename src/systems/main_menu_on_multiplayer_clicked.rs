@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::{AppState, MainMenuSelectedButton, MultiplayerButton};
+
+pub fn main_menu_on_multiplayer_clicked(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<MultiplayerButton>)>,
+    mut selected_button: ResMut<MainMenuSelectedButton>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(AppState::Lobby);
+            }
+            Interaction::Hovered => {
+                *selected_button = MainMenuSelectedButton::Multiplayer;
+            }
+            Interaction::None => {}
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::{MainMenuSelectedButton, PlayButton, RestartEvent};
+
+pub fn main_menu_on_play_clicked(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlayButton>)>,
+    mut selected_button: ResMut<MainMenuSelectedButton>,
+    mut ev_restart: EventWriter<RestartEvent>,
+) {
+    for interaction in &interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_restart.send_default();
+            }
+            Interaction::Hovered => {
+                *selected_button = MainMenuSelectedButton::Play;
+            }
+            Interaction::None => {}
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{MainMenuSelectedButton, QuitButton};
+
+pub fn main_menu_on_quit_clicked(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
+    mut selected_button: ResMut<MainMenuSelectedButton>,
+    mut ev_exit: EventWriter<AppExit>,
+) {
+    for interaction in &interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_exit.send_default();
+            }
+            Interaction::Hovered => {
+                *selected_button = MainMenuSelectedButton::Quit;
+            }
+            Interaction::None => {}
+        }
+    }
+}
@@ -16,18 +16,9 @@ pub use move_food::move_food;
 mod player_input;
 pub use player_input::player_input;
 
-mod increase_hunger;
-pub use increase_hunger::increase_hunger;
-
-mod increase_speed;
-pub use increase_speed::increase_speed;
-
 mod upgrade_menu_event_handler;
 pub use upgrade_menu_event_handler::upgrade_menu_event_handler;
 
-mod split_snake;
-pub use split_snake::split_snake;
-
 mod upgrade_menu_handler;
 pub use upgrade_menu_handler::upgrade_menu_handler;
 
@@ -45,3 +36,279 @@ pub use debug_output_shown::debug_output_shown;
 
 mod spawn_pause_menu;
 pub use spawn_pause_menu::spawn_pause_menu;
+
+mod spawn_main_menu;
+pub use spawn_main_menu::spawn_main_menu;
+
+mod main_menu_navigation;
+pub use main_menu_navigation::main_menu_navigation;
+
+mod close_upgrades_menu;
+pub use close_upgrades_menu::close_upgrades_menu;
+
+mod move_snake;
+pub use move_snake::move_snake;
+
+mod spawn_snake_visuals;
+pub use spawn_snake_visuals::spawn_snake_visuals;
+
+mod spawn_tail_node_visuals;
+pub use spawn_tail_node_visuals::spawn_tail_node_visuals;
+
+mod spawn_food_visuals;
+pub use spawn_food_visuals::spawn_food_visuals;
+
+mod spawn_coins_visuals;
+pub use spawn_coins_visuals::spawn_coins_visuals;
+
+mod pause_game;
+pub use pause_game::pause_game;
+
+mod pause_menu_event_handler;
+pub use pause_menu_event_handler::pause_menu_event_handler;
+
+mod game_over_menu_navigation;
+pub use game_over_menu_navigation::game_over_menu_navigation;
+
+mod pause_menu_selected_button_update;
+pub use pause_menu_selected_button_update::pause_menu_selected_button_update;
+
+mod game_over_menu_selected_button_update;
+pub use game_over_menu_selected_button_update::game_over_menu_selected_button_update;
+
+mod show_game_over;
+pub use show_game_over::show_game_over;
+
+mod restart;
+pub use restart::restart;
+
+mod game_over_on_restart_clicked;
+pub use game_over_on_restart_clicked::game_over_on_restart_clicked;
+
+mod on_upgrade_clicked;
+pub use on_upgrade_clicked::on_upgrade_clicked;
+
+mod pause_menu_on_quit_clicked;
+pub use pause_menu_on_quit_clicked::pause_menu_on_quit_clicked;
+
+mod pause_menu_on_upgrades_clicked;
+pub use pause_menu_on_upgrades_clicked::pause_menu_on_upgrades_clicked;
+
+mod game_over_on_quit_clicked;
+pub use game_over_on_quit_clicked::game_over_on_quit_clicked;
+
+mod main_menu_on_play_clicked;
+pub use main_menu_on_play_clicked::main_menu_on_play_clicked;
+
+mod main_menu_on_quit_clicked;
+pub use main_menu_on_quit_clicked::main_menu_on_quit_clicked;
+
+mod spawn_game_over_splash;
+pub use spawn_game_over_splash::spawn_game_over_splash;
+
+mod move_tail;
+pub use move_tail::move_tail;
+
+mod consume_items;
+pub use consume_items::consume_items;
+
+mod update_debug_output;
+pub use update_debug_output::update_debug_output;
+
+mod spawn_debug_output;
+pub use spawn_debug_output::spawn_debug_output;
+
+mod coinbag_leak;
+pub use coinbag_leak::coinbag_leak;
+
+mod drag;
+pub use drag::drag;
+
+mod spawn_coins_output;
+pub use spawn_coins_output::spawn_coins_output;
+
+mod spawn_game_field_quad;
+pub use spawn_game_field_quad::spawn_game_field_quad;
+
+mod spawn_score_output;
+pub use spawn_score_output::spawn_score_output;
+
+mod setup;
+pub use setup::setup;
+
+mod spawn_upgrades_menu;
+pub use spawn_upgrades_menu::spawn_upgrades_menu;
+
+mod spawn_food;
+pub use spawn_food::spawn_food;
+
+mod restore_physical_translation;
+pub use restore_physical_translation::restore_physical_translation;
+
+mod store_physical_translation;
+pub use store_physical_translation::store_physical_translation;
+
+mod interpolate_translation;
+pub use interpolate_translation::interpolate_translation;
+
+mod advance_tick;
+pub use advance_tick::advance_tick;
+
+mod record_replay_input;
+pub use record_replay_input::record_replay_input;
+
+mod play_back_replay;
+pub use play_back_replay::play_back_replay;
+
+mod finish_replay;
+pub use finish_replay::finish_replay;
+
+mod save_replay;
+pub use save_replay::save_replay;
+
+mod buy_upgrade;
+pub use buy_upgrade::buy_upgrade;
+
+mod save_game;
+pub use save_game::save_game;
+
+mod load_game;
+pub use load_game::load_game;
+
+mod pause_menu_on_save_clicked;
+pub use pause_menu_on_save_clicked::pause_menu_on_save_clicked;
+
+mod main_menu_on_continue_clicked;
+pub use main_menu_on_continue_clicked::main_menu_on_continue_clicked;
+
+mod apply_settings;
+pub use apply_settings::apply_settings;
+
+mod save_settings;
+pub use save_settings::save_settings;
+
+mod spawn_settings_menu;
+pub use spawn_settings_menu::spawn_settings_menu;
+
+mod update_settings_menu;
+pub use update_settings_menu::update_settings_menu;
+
+mod settings_menu_navigation;
+pub use settings_menu_navigation::settings_menu_navigation;
+
+mod pause_menu_on_settings_clicked;
+pub use pause_menu_on_settings_clicked::pause_menu_on_settings_clicked;
+
+mod update_action_state;
+pub use update_action_state::update_action_state;
+
+mod save_bindings;
+pub use save_bindings::save_bindings;
+
+mod spawn_controls_menu;
+pub use spawn_controls_menu::spawn_controls_menu;
+
+mod update_controls_menu;
+pub use update_controls_menu::update_controls_menu;
+
+mod controls_menu_navigation;
+pub use controls_menu_navigation::controls_menu_navigation;
+
+mod spawn_lobby;
+pub use spawn_lobby::spawn_lobby;
+
+mod update_lobby;
+pub use update_lobby::update_lobby;
+
+mod lobby_navigation;
+pub use lobby_navigation::lobby_navigation;
+
+mod collide_with_tails;
+pub use collide_with_tails::collide_with_tails;
+
+mod handle_snake_deaths;
+pub use handle_snake_deaths::handle_snake_deaths;
+
+mod main_menu_on_multiplayer_clicked;
+pub use main_menu_on_multiplayer_clicked::main_menu_on_multiplayer_clicked;
+
+mod reset_local_players;
+pub use reset_local_players::reset_local_players;
+
+mod steer_rivals;
+pub use steer_rivals::steer_rivals;
+
+mod capture_net_input;
+pub use capture_net_input::capture_net_input;
+
+mod sync_net_inputs;
+pub use sync_net_inputs::sync_net_inputs;
+
+mod checksum_net_state;
+pub use checksum_net_state::checksum_net_state;
+
+mod sync_net_session;
+pub use sync_net_session::sync_net_session;
+
+mod apply_upgrade_effect;
+pub use apply_upgrade_effect::apply_upgrade_effect;
+
+mod load_levels;
+pub use load_levels::load_levels;
+
+mod reload_levels;
+pub use reload_levels::reload_levels;
+
+mod load_upgrades;
+pub use load_upgrades::load_upgrades;
+
+mod reload_upgrades;
+pub use reload_upgrades::reload_upgrades;
+
+mod update_upgrade_labels;
+pub use update_upgrade_labels::update_upgrade_labels;
+
+mod update_upgrade_details;
+pub use update_upgrade_details::update_upgrade_details;
+
+mod show_purchase_feedback;
+pub use show_purchase_feedback::show_purchase_feedback;
+
+mod shake_upgrade_icons;
+pub use shake_upgrade_icons::shake_upgrade_icons;
+
+mod upgrades_menu_navigation;
+pub use upgrades_menu_navigation::upgrades_menu_navigation;
+
+mod update_ability_output;
+pub use update_ability_output::update_ability_output;
+
+mod use_abilities;
+pub use use_abilities::use_abilities;
+
+mod spawn_ability_output;
+pub use spawn_ability_output::spawn_ability_output;
+
+mod update_status_effect_icons;
+pub use update_status_effect_icons::update_status_effect_icons;
+
+mod tick_status_effects;
+pub use tick_status_effects::tick_status_effects;
+
+mod pull_items_to_magnets;
+pub use pull_items_to_magnets::pull_items_to_magnets;
+
+mod update_food_awareness;
+pub use update_food_awareness::update_food_awareness;
+
+mod show_food_alerts;
+pub use show_food_alerts::show_food_alerts;
+
+mod collide_with_obstacles;
+pub use collide_with_obstacles::collide_with_obstacles;
+
+mod spawn_obstacle_visuals;
+pub use spawn_obstacle_visuals::spawn_obstacle_visuals;
+
+mod spawn_level;
+pub use spawn_level::spawn_level;
@@ -1,54 +1,105 @@
-use bevy::prelude::*;
+use std::f32::consts::TAU;
 
-use crate::{
-    Food,
-    Velocity,
-    RandNormalized,
-    GameFieldSize,
-    FOOD_RADIUS,
-    Snake,
-};
+use bevy::prelude::*;
+use rand::Rng;
 
-use rand::random;
+use crate::{levels::Level, Awareness, Food, GameRng, Obstacle, Snake, Velocity};
 
-const FOOD_MOVE_SPEED: f32 = 200.;
+// how fast a mouse pottering about goes, as a fraction of how fast it can run
+const WANDER_SPEED_FRACTION: f32 = 0.35;
+// the most a wandering mouse changes its heading by in a second, either way
+const WANDER_TURN_RATE: f32 = 4.;
+// how quickly a mouse gets up to the velocity it wants, per second
+const STEERING_RATE: f32 = 6.;
+// how close to a wall a mouse starts turning away from it, and how much that matters compared to
+// getting away from a snake, so it doesn't end up cornered
+const WALL_AVOID_DISTANCE: f32 = 150.;
+const WALL_AVOID_WEIGHT: f32 = 2.;
 
-fn should_change_direction(chance: f32) -> bool {
-    random::<f32>() < chance
-}
-
-// this doesn't work as expected and I have no idea why, it's behaving very bizarrely
+/// Mice wander about until they notice a snake, then run away from the nearest one, turning along
+/// the walls rather than getting stuck against them, and sliding round obstacles
 pub fn move_food(
-    mut food: Query<(&mut Transform, &mut Velocity), With<Food>>,
-    mut snake: Query<&Transform, (With<Snake>, Without<Food>)>,
+    mut food: Query<(&mut Transform, &mut Velocity, &Food, &Awareness)>,
+    snakes: Query<&Transform, (With<Snake>, Without<Food>)>,
+    obstacles: Query<(&Transform, &Obstacle), Without<Food>>,
     time: Res<Time>,
-    gamefield_size: Res<GameFieldSize>,
+    level: Res<Level>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let snake = snake.single_mut();
-    let (mut food_transform, mut food_velocity) = food.single_mut();
-    let Velocity(ref mut food_velocity) = *food_velocity;
-    let distance = food_transform.translation.distance(snake.translation);
-    if should_change_direction((1./distance) * 10.) {
-        *food_velocity = Vec3::random();
-        food_velocity.x -= 0.5;
-        food_velocity.y -= 0.5;
-        *food_velocity *= FOOD_MOVE_SPEED * 2.;
-        food_velocity.z = 0.;
-        let rotate_to_velocity = Quat::from_rotation_arc(Vec3::Z, *food_velocity);
-        food_transform.rotation = rotate_to_velocity;
-    }
-    let next_loc = food_transform.translation + *food_velocity;
-    let mut boundary = Vec2::new(gamefield_size.x, gamefield_size.y);
-    boundary.x /= 2.;
-    boundary.y /= 2.;
-    if 
-        next_loc.x < -boundary.x + FOOD_RADIUS ||
-        next_loc.x > boundary.x - FOOD_RADIUS  ||
-        next_loc.y < -boundary.y + FOOD_RADIUS ||
-        next_loc.y > boundary.y - FOOD_RADIUS
-    {
-        // food should "bump" into the wall
-        *food_velocity = Vec3::ZERO;
+    let delta = time.delta_seconds();
+    // oldest first, so they draw from the rng in the same order whatever order the query is in
+    let mut food: Vec<_> = food.iter_mut().collect();
+    food.sort_by_key(|(_, _, food, _)| food.spawned);
+    for (mut food_transform, mut food_velocity, Food { kind, .. }, awareness) in food {
+        let Velocity(ref mut food_velocity) = *food_velocity;
+        let stats = kind.stats();
+        let position = food_transform.translation.truncate();
+        let velocity = food_velocity.truncate();
+
+        // only the closest snake bothers it
+        let threat = snakes
+            .iter()
+            .map(|snake| snake.translation.truncate())
+            .filter(|_| awareness.alerted)
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+        // always drawn, so the rng moves on the same whether it's running or not
+        let roll: f32 = rng.gen();
+        let (mut heading, speed) = match threat {
+            Some(head) => ((position - head).normalize_or_zero(), stats.speed),
+            None => {
+                let heading = match velocity.try_normalize() {
+                    Some(heading) => {
+                        let turn = (roll - 0.5) * 2. * WANDER_TURN_RATE * delta;
+                        Vec2::from_angle(turn).rotate(heading)
+                    }
+                    // standing still, so off in any direction
+                    None => Vec2::from_angle(roll * TAU),
+                };
+                (heading, stats.speed * WANDER_SPEED_FRACTION)
+            }
+        };
+
+        // walls push it back towards the middle, harder the closer it gets
+        let boundary = level.field_size / 2. - stats.radius;
+        let closeness = |gap: f32| (1. - gap / WALL_AVOID_DISTANCE).max(0.);
+        let away_from_walls = Vec2::new(
+            closeness(position.x + boundary.x) - closeness(boundary.x - position.x),
+            closeness(position.y + boundary.y) - closeness(boundary.y - position.y),
+        );
+        heading = (heading + away_from_walls * WALL_AVOID_WEIGHT).normalize_or_zero();
+
+        // turns towards where it wants to go, rather than snapping round
+        let steering = (STEERING_RATE * delta).min(1.);
+        let mut velocity = velocity.lerp(heading * speed, steering);
+
+        // slides along a wall it runs into, instead of through it
+        let mut next = position + velocity * delta;
+        if next.x.abs() > boundary.x {
+            velocity.x = 0.;
+            next.x = next.x.clamp(-boundary.x, boundary.x);
+        }
+        if next.y.abs() > boundary.y {
+            velocity.y = 0.;
+            next.y = next.y.clamp(-boundary.y, boundary.y);
+        }
+        // and round obstacles
+        for (obstacle_transform, obstacle) in &obstacles {
+            let center = obstacle_transform.translation.truncate();
+            if let Some(push) = obstacle.shape.push_out(center, next, stats.radius) {
+                next += push;
+                let normal = push.normalize_or_zero();
+                velocity -= normal * velocity.dot(normal).min(0.);
+            }
+        }
+
+        *food_velocity = velocity.extend(0.);
+        food_transform.translation = next.extend(food_transform.translation.z);
+        // the sprite faces up
+        if let Some(facing) = velocity.try_normalize() {
+            food_transform.rotation = Quat::from_rotation_arc_2d(Vec2::Y, facing);
+        }
     }
-    food_transform.translation += *food_velocity * time.delta_seconds();
 }
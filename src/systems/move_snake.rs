@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::{
+    levels::Level, DeathCause, Snake, SnakeControls, SnakeDiedEvent, SnakeSpeed, StatusEffect,
+    StatusEffects, Velocity, SNAKE_HEAD_RADIUS,
+};
+
+pub fn move_snake(
+    time: Res<Time>,
+    mut snakes: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &SnakeControls,
+            &SnakeSpeed,
+            &StatusEffects,
+        ),
+        With<Snake>,
+    >,
+    mut ev_snake_died: EventWriter<SnakeDiedEvent>,
+    level: Res<Level>,
+) {
+    for (entity, mut head_transform, mut head_velocity, controls, snake_speed, status_effects) in
+        &mut snakes
+    {
+        let Velocity(ref mut head_velocity) = *head_velocity;
+        let speed_multiplier = status_effects.speed_multiplier();
+        let accel_factor = snake_speed.discrete * speed_multiplier;
+        let analog_accel_factor = snake_speed.analog * speed_multiplier;
+
+        let mut player_requested_velocity = Vec3::from((controls.stick, 0.));
+        player_requested_velocity *= time.delta_seconds() * analog_accel_factor;
+        *head_velocity += player_requested_velocity;
+
+        let head_velocity_delta = Vec3::from((controls.direction, 0.));
+        *head_velocity += head_velocity_delta.normalize_or_zero() * accel_factor;
+
+        head_transform.translation += *head_velocity * time.delta_seconds();
+        let invulnerable = status_effects.has(StatusEffect::Invulnerable);
+        let mut boundary_x = level.field_size.x / 2.;
+        let mut boundary_y = level.field_size.y / 2.;
+        boundary_x -= SNAKE_HEAD_RADIUS;
+        boundary_y -= SNAKE_HEAD_RADIUS;
+        if !invulnerable
+            && (head_transform.translation.x > boundary_x
+                || head_transform.translation.y > boundary_y
+                || head_transform.translation.x < -boundary_x
+                || head_transform.translation.y < -boundary_y)
+        {
+            // game over
+            ev_snake_died.send(SnakeDiedEvent {
+                snake: entity,
+                cause: DeathCause::HitWall,
+            });
+        }
+        head_transform.translation.x = head_transform.translation.x.clamp(-boundary_x, boundary_x);
+        head_transform.translation.y = head_transform.translation.y.clamp(-boundary_y, boundary_y);
+    }
+}
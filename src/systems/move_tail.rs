@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::{Snake, SnakeTailNode, TailNodeCount, TailOf, TAIL_NODE_GAP};
+
+const TAIL_CATCHUP_SPEED: f32 = 7.;
+
+pub fn move_tail(
+    time: Res<Time>,
+    mut tail_nodes: Query<(&mut Transform, &TailOf, &TailNodeCount), With<SnakeTailNode>>,
+    snakes: Query<(Entity, &Transform), (With<Snake>, Without<SnakeTailNode>)>,
+) {
+    for (snake, head) in &snakes {
+        let mut tail: Vec<_> = tail_nodes
+            .iter_mut()
+            .filter(|(_, TailOf(owner), _)| *owner == snake)
+            .collect();
+        tail.sort_by_key(|(_, _, TailNodeCount(index))| *index);
+        let mut target_point = head.translation;
+        for (tail_node, _, _) in &mut tail {
+            if tail_node.translation.distance(target_point) >= TAIL_NODE_GAP {
+                let bearing = target_point - tail_node.translation;
+                tail_node.translation += bearing * time.delta_seconds() * TAIL_CATCHUP_SPEED;
+            }
+
+            target_point = tail_node.translation;
+        }
+    }
+}
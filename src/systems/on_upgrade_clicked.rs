@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::{
+    buy_upgrade, replay::ReplayRecorder, Game, PurchaseOutcome, UpgradeIconClickedEvent,
+    UpgradePurchaseEvent, Upgrades,
+};
+
+pub fn on_upgrade_clicked(
+    mut commands: Commands,
+    mut game: ResMut<Game>,
+    mut upgrades: ResMut<Upgrades>,
+    mut ev_upgrade_clicked: EventReader<UpgradeIconClickedEvent>,
+    mut ev_purchase: EventWriter<UpgradePurchaseEvent>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    for ev in ev_upgrade_clicked.read() {
+        let Some(upgrade) = upgrades.by_id_mut(ev.icon.upgrade_id) else {
+            continue;
+        };
+        let outcome = if buy_upgrade(&mut commands, &mut game, upgrade) {
+            let tick = game.ticks;
+            recorder.record_purchase(tick, upgrade.id);
+            PurchaseOutcome::Bought
+        } else if upgrade.maxed_out() {
+            PurchaseOutcome::MaxedOut
+        } else {
+            PurchaseOutcome::TooExpensive
+        };
+        ev_purchase.send(UpgradePurchaseEvent {
+            upgrade_id: upgrade.id,
+            outcome,
+        });
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::{net::NetSession, AppState, PauseGameEvent};
+
+pub fn pause_game(
+    mut ev_paused: EventReader<PauseGameEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+    session: Option<Res<NetSession>>,
+) {
+    if !ev_paused.is_empty() {
+        ev_paused.clear();
+        // the other player's game would carry on without us
+        if session.is_some() {
+            info!("Online matches can't be paused");
+            return;
+        }
+        next_state.set(AppState::Paused);
+    }
+}
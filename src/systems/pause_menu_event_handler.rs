@@ -0,0 +1,53 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    actions::Action, actions::ActionState, AppState, PauseMenuButton, PauseMenuSelectedButton,
+    SaveAndQuitEvent, UpgradeMenuButtonClickedEvent,
+};
+
+pub fn pause_menu_event_handler(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut actions: ResMut<ActionState>,
+    mut selected_button: ResMut<PauseMenuSelectedButton>,
+    mut ev_quit: EventWriter<AppExit>,
+    mut ev_upgrades_menu: EventWriter<UpgradeMenuButtonClickedEvent>,
+    mut ev_save_and_quit: EventWriter<SaveAndQuitEvent>,
+    mut button_styles: Query<(&PauseMenuButton, &mut Style)>,
+) {
+    for (PauseMenuButton(button), mut style) in &mut button_styles {
+        if *button == *selected_button {
+            style.border.bottom = Val::Px(2.);
+            style.margin.bottom = Val::Px(-2.);
+        } else {
+            style.border.bottom = Val::ZERO;
+            style.margin.bottom = Val::ZERO;
+        }
+    }
+    if actions.clear_just_pressed(Action::Pause) | actions.clear_just_pressed(Action::Back) {
+        next_state.set(AppState::Playing);
+    }
+    if actions.clear_just_pressed(Action::MenuUp) {
+        selected_button.prev();
+    }
+    if actions.clear_just_pressed(Action::MenuDown) {
+        selected_button.next();
+    }
+    let confirmed = actions.clear_just_pressed(Action::Confirm);
+    if confirmed {
+        match *selected_button {
+            PauseMenuSelectedButton::Upgrades => {
+                ev_upgrades_menu.send_default();
+            }
+            PauseMenuSelectedButton::Settings => {
+                next_state.set(AppState::Settings);
+            }
+            PauseMenuSelectedButton::SaveAndQuit => {
+                ev_save_and_quit.send_default();
+            }
+            PauseMenuSelectedButton::Quit => {
+                ev_quit.send_default();
+            }
+            PauseMenuSelectedButton::None => {}
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{PauseMenuButtonHighlightedEvent, PauseMenuSelectedButton, QuitButton};
+
+pub fn pause_menu_on_quit_clicked(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
+    mut ev_select_button: EventWriter<PauseMenuButtonHighlightedEvent>,
+    mut ev_exit: EventWriter<AppExit>,
+) {
+    for interaction in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_exit.send_default();
+            }
+            Interaction::Hovered => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::Quit,
+                ));
+            }
+            Interaction::None => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::None,
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::{
+    PauseMenuButtonHighlightedEvent, PauseMenuSelectedButton, SaveAndQuitButton, SaveAndQuitEvent,
+};
+
+pub fn pause_menu_on_save_clicked(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<SaveAndQuitButton>)>,
+    mut ev_select_button: EventWriter<PauseMenuButtonHighlightedEvent>,
+    mut ev_save_and_quit: EventWriter<SaveAndQuitEvent>,
+) {
+    for interaction in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_save_and_quit.send_default();
+            }
+            Interaction::Hovered => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::SaveAndQuit,
+                ));
+            }
+            Interaction::None => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::None,
+                ));
+            }
+        }
+    }
+}
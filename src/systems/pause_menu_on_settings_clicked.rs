@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::{AppState, PauseMenuButtonHighlightedEvent, PauseMenuSelectedButton, SettingsButton};
+
+pub fn pause_menu_on_settings_clicked(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut ev_select_button: EventWriter<PauseMenuButtonHighlightedEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for interaction in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(AppState::Settings);
+            }
+            Interaction::Hovered => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::Settings,
+                ));
+            }
+            Interaction::None => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::None,
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::{
+    PauseMenuButtonHighlightedEvent, PauseMenuSelectedButton, UpgradeMenuButtonClickedEvent,
+    UpgradesButton,
+};
+
+pub fn pause_menu_on_upgrades_clicked(
+    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<UpgradesButton>)>,
+    mut ev_select_button: EventWriter<PauseMenuButtonHighlightedEvent>,
+    mut ev_upgrades_menu: EventWriter<UpgradeMenuButtonClickedEvent>,
+) {
+    for interaction in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                ev_upgrades_menu.send_default();
+            }
+            Interaction::Hovered => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::Upgrades,
+                ));
+            }
+            Interaction::None => {
+                ev_select_button.send(PauseMenuButtonHighlightedEvent(
+                    PauseMenuSelectedButton::None,
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::{PauseMenuButtonHighlightedEvent, PauseMenuSelectedButton};
+
+// the buttons themselves are restyled to match by pause_menu_event_handler
+pub fn pause_menu_selected_button_update(
+    mut highlighted_button: ResMut<PauseMenuSelectedButton>,
+    mut ev_button_highlighted: EventReader<PauseMenuButtonHighlightedEvent>,
+) {
+    for selected_button in ev_button_highlighted.read() {
+        *highlighted_button = selected_button.0.clone();
+    }
+}
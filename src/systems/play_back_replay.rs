@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{
+    buy_upgrade,
+    replay::{ReplayPlayback, ReplayRecorder},
+    Game, Snake, SnakeControls, Upgrades,
+};
+
+pub fn play_back_replay(
+    mut commands: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut snakes: Query<(&Snake, &mut SnakeControls)>,
+    mut game: ResMut<Game>,
+    mut upgrades: ResMut<Upgrades>,
+) {
+    let tick = game.ticks;
+    // replays only ever have the one player
+    if let Some((_, mut controls)) = snakes.iter_mut().find(|(snake, _)| snake.player == 0) {
+        playback.apply_controls(tick, &mut controls);
+    }
+    for upgrade_id in playback.take_purchases(tick) {
+        let Some(upgrade) = upgrades.by_id_mut(upgrade_id) else {
+            warn!("Replay bought upgrade {upgrade_id}, which doesn't exist");
+            continue;
+        };
+        if buy_upgrade(&mut commands, &mut game, upgrade) {
+            recorder.record_purchase(tick, upgrade_id);
+        } else {
+            warn!(
+                "Replay bought {} without enough money, it has desynced",
+                upgrade.name
+            );
+        }
+    }
+}
@@ -1,49 +1,95 @@
 use bevy::prelude::*;
 
 use crate::{
-    Velocity,
-    Snake,
-    GameOverEvent,
-    PauseGameEvent,
-    DebugSettings,
-    DebugOutput,
-    GameFieldSize,
-    CameraSettings,
-    SNAKE_HEAD_RADIUS,
-    SnakeSpeed,
+    actions::{Action, ActionState, InputBindings, InputDevice, RawInput},
+    settings::Settings,
+    Ability, CameraSettings, DebugOutput, DebugSettings, LocalPlayers, PauseGameEvent, Rival,
+    Snake, SnakeControls,
 };
 
 pub fn player_input(
-    time: Res<Time>,
-    mut snake: Query<(&mut Transform, &mut Velocity), With<Snake>>,
-    mut keys: ResMut<Input<KeyCode>>,
+    mut snakes: Query<(&Transform, &Snake, &mut SnakeControls), Without<Rival>>,
+    mut actions: ResMut<ActionState>,
+    bindings: Res<InputBindings>,
+    players: Res<LocalPlayers>,
+    keys: Res<Input<KeyCode>>,
     gamepads: Res<Gamepads>,
     axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<Input<GamepadButton>>,
     button_axes: Res<Axis<GamepadButton>>,
-    mut buttons: ResMut<Input<GamepadButton>>,
-    mut ev_gameover: EventWriter<GameOverEvent>,
     mut ev_pause: EventWriter<PauseGameEvent>,
     mut debug_settings: ResMut<DebugSettings>,
     mut debug_output_visibility: Query<&mut Visibility, With<DebugOutput>>,
-    mut camera_projection: Query<(&mut OrthographicProjection, &mut Transform), (With<Camera2d>, Without<Snake>)>,
-    gamefield_size: Res<GameFieldSize>,
+    mut camera_projection: Query<
+        (&mut OrthographicProjection, &mut Transform),
+        (With<Camera2d>, Without<Snake>),
+    >,
     mut camera_settings: ResMut<CameraSettings>,
-    snake_speed: Res<SnakeSpeed>,
+    settings: Res<Settings>,
 ) {
-    let gamepad = gamepads.iter().next();
-    let (mut head_transform, mut head_velocity) = snake.single_mut();
-    let Velocity(ref mut head_velocity) = *head_velocity;
-    let accel_factor = snake_speed.discrete;
-    let analog_accel_factor = snake_speed.analog;
-    if let Some(gamepad) = gamepad {
-        let axis_x = GamepadAxis {
-            gamepad,
-            axis_type: GamepadAxisType::LeftStickX,
+    let input = RawInput {
+        keys: &keys,
+        gamepads: &gamepads,
+        buttons: &buttons,
+        button_axes: &button_axes,
+    };
+    let (mut camera_projection, mut camera_transform) = camera_projection.single_mut();
+    // each snake only listens to its own player's device
+    for (_, snake, mut controls) in &mut snakes {
+        // remote players are steered by `sync_net_inputs` instead
+        let Some(device) = players.devices.get(snake.player).copied() else {
+            continue;
         };
-        let axis_y = GamepadAxis {
-            gamepad,
-            axis_type: GamepadAxisType::LeftStickY,
+        if device == InputDevice::Network {
+            continue;
+        }
+        controls.stick = Vec2::ZERO;
+        // the sticks are analog, so they aren't actions
+        let gamepad = match device {
+            InputDevice::Any => gamepads.iter().next(),
+            InputDevice::Keyboard | InputDevice::Network => None,
+            InputDevice::Gamepad(gamepad) => Some(gamepad),
         };
+        if let Some(gamepad) = gamepad {
+            let axis_x = GamepadAxis {
+                gamepad,
+                axis_type: GamepadAxisType::LeftStickX,
+            };
+            let axis_y = GamepadAxis {
+                gamepad,
+                axis_type: GamepadAxisType::LeftStickY,
+            };
+            if let (Some(x), Some(y)) = (axes.get(axis_x), axes.get(axis_y)) {
+                // combine X and Y into one vector
+                let stick = Vec2::new(x, y);
+                if stick.length() > settings.move_deadzone {
+                    controls.stick = stick;
+                }
+            }
+        }
+        let mut head_velocity_delta = Vec2::ZERO;
+        if bindings.pressed_on(Action::MoveUp, device, &input) {
+            head_velocity_delta.y += 1.;
+        }
+        if bindings.pressed_on(Action::MoveDown, device, &input) {
+            head_velocity_delta.y -= 1.;
+        }
+        if bindings.pressed_on(Action::MoveRight, device, &input) {
+            head_velocity_delta.x += 1.;
+        }
+        if bindings.pressed_on(Action::MoveLeft, device, &input) {
+            head_velocity_delta.x -= 1.;
+        }
+        controls.direction = head_velocity_delta;
+        // kept until `use_abilities` picks it up, so a press between two ticks isn't lost
+        for ability in Ability::ALL {
+            if bindings.just_pressed_on(ability.action(), device, &input) {
+                controls.ability = Some(ability);
+            }
+        }
+    }
+    // the camera is shared, so anyone's right stick can move it
+    for gamepad in gamepads.iter() {
         let axis_rx = GamepadAxis {
             gamepad,
             axis_type: GamepadAxisType::RightStickX,
@@ -52,65 +98,34 @@ pub fn player_input(
             gamepad,
             axis_type: GamepadAxisType::RightStickY,
         };
-        let axis_rt = GamepadButton {
-            gamepad,
-            button_type: GamepadButtonType::RightTrigger2,
-        };
-        let axis_lt = GamepadButton {
-            gamepad,
-            button_type: GamepadButtonType::LeftTrigger2,
-        };
-        if let (Some(x), Some(y)) = (axes.get(axis_x), axes.get(axis_y)) {
-            // combine X and Y into one vector
-            let left_stick_pos = Vec2::new(x, y);
-            let mut player_requested_velocity = Vec3::from((left_stick_pos, 0.));
-            player_requested_velocity *= time.delta_seconds() * analog_accel_factor;
-            *head_velocity = *head_velocity + player_requested_velocity;
-        }
-        let (mut camera_projection, mut camera_transform) = camera_projection.single_mut();
-        if let (Some(rt), Some(lt)) = (button_axes.get(axis_rt), button_axes.get(axis_lt)) {
-            let total_zoom = (lt - rt).clamp(-0.7, 1.0);
-            camera_projection.scale = 1.0 + total_zoom;
-        }
         if let (Some(x), Some(y)) = (axes.get(axis_rx), axes.get(axis_ry)) {
             let right_stick_pos = Vec3::new(x, y, 0.);
-            // apply a deadzone to prevent stick drift, deadzone is hardcoded here because fuck
-            // you, that's why.
-            if right_stick_pos.length() > 0.1 {
-                // todo: make camera sensitivity configurable, once we have a Settings menu
-                camera_transform.translation += right_stick_pos * 5.;
+            // apply a deadzone to prevent stick drift
+            if right_stick_pos.length() > settings.camera_deadzone {
+                camera_transform.translation += right_stick_pos * settings.camera_sensitivity;
             }
         }
-        let r3_button = GamepadButton {
-            gamepad,
-            button_type: GamepadButtonType::RightThumb,
-        };
-        if buttons.clear_just_pressed(r3_button) {
+    }
+    let total_zoom = actions.value(Action::ZoomOut) - actions.value(Action::ZoomIn);
+    camera_projection.scale = (1.0 + total_zoom).clamp(settings.min_zoom, settings.max_zoom);
+    if actions.clear_just_pressed(Action::ResetCamera) {
+        camera_transform.translation = Vec3::ZERO;
+    }
+    if actions.clear_just_pressed(Action::ToggleCamera) {
+        camera_settings.follow_snake = !camera_settings.follow_snake;
+        if !camera_settings.follow_snake {
             camera_transform.translation = Vec3::ZERO;
         }
-        if camera_settings.follow_snake {
-            let camera_origin = head_transform.translation;
+    }
+    if camera_settings.follow_snake {
+        // keep everyone in the middle of the screen
+        let heads: Vec<_> = snakes.iter().map(|(head, _, _)| head.translation).collect();
+        if !heads.is_empty() {
+            let camera_origin = heads.iter().sum::<Vec3>() / heads.len() as f32;
             camera_transform.translation = camera_origin;
-        } 
-        let start_button = GamepadButton {
-            gamepad,
-            button_type: GamepadButtonType::Start,
-        };
-        let select_button = GamepadButton {
-            gamepad,
-            button_type: GamepadButtonType::Select,
-        };
-        if buttons.clear_just_pressed(select_button) {
-            camera_settings.follow_snake = !camera_settings.follow_snake;
-            if !camera_settings.follow_snake {
-                camera_transform.translation = Vec3::ZERO;
-            }
-        }
-        if buttons.clear_just_pressed(start_button) {
-            ev_pause.send_default();
         }
     }
-    if keys.clear_just_pressed(KeyCode::F3) {
+    if actions.clear_just_pressed(Action::ToggleDebug) {
         debug_settings.output_shown = !debug_settings.output_shown;
         let mut debug_output_visibility = debug_output_visibility.single_mut();
         *debug_output_visibility = match debug_settings.output_shown {
@@ -118,37 +133,7 @@ pub fn player_input(
             false => Visibility::Hidden,
         }
     }
-    let mut head_velocity_delta = Vec3::ZERO;
-    if keys.pressed(KeyCode::W) || keys.pressed(KeyCode::Up) {
-        head_velocity_delta.y += 1.;
-    }
-    if keys.pressed(KeyCode::S) || keys.pressed(KeyCode::Down) {
-        head_velocity_delta.y -= 1.;
-    }
-    if keys.pressed(KeyCode::D) || keys.pressed(KeyCode::Right) {
-        head_velocity_delta.x += 1.;
-    }
-    if keys.pressed(KeyCode::A) || keys.pressed(KeyCode::Left) {
-        head_velocity_delta.x -= 1.;
-    }
-    *head_velocity = *head_velocity + (head_velocity_delta.normalize_or_zero() * accel_factor);
-
-    if keys.clear_just_pressed(KeyCode::P) || keys.clear_just_pressed(KeyCode::Escape) {
+    if actions.clear_just_pressed(Action::Pause) {
         ev_pause.send_default();
     }
-    head_transform.translation += *head_velocity * time.delta_seconds();
-    let mut boundary_x = gamefield_size.x / 2.;
-    let mut boundary_y = gamefield_size.y / 2.;
-    boundary_x -= SNAKE_HEAD_RADIUS;
-    boundary_y -= SNAKE_HEAD_RADIUS;
-    if head_transform.translation.x > boundary_x
-        || head_transform.translation.y > boundary_y
-        || head_transform.translation.x < -boundary_x
-        || head_transform.translation.y < -boundary_y
-    {
-        // game over
-        ev_gameover.send_default();
-    }
-    head_transform.translation.x = head_transform.translation.x.clamp(-boundary_x, boundary_x);
-    head_transform.translation.y = head_transform.translation.y.clamp(-boundary_y, boundary_y);
 }
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::{CoinBag, Food, Snake, StatusEffect, StatusEffects};
+
+// how close food and coin bags have to be before a magnet starts pulling them in
+const MAGNET_RADIUS: f32 = 300.;
+const MAGNET_PULL_SPEED: f32 = 400.;
+
+/// Drags food and coin bags towards the nearest snake with a magnet on it
+pub fn pull_items_to_magnets(
+    time: Res<Time>,
+    snakes: Query<(&Transform, &Snake, &StatusEffects)>,
+    mut items: Query<&mut Transform, (Or<(With<Food>, With<CoinBag>)>, Without<Snake>)>,
+) {
+    // in player order, so whoever is first in the list wins a tie
+    let mut magnets: Vec<_> = snakes
+        .iter()
+        .filter(|(_, _, status_effects)| status_effects.has(StatusEffect::Magnet))
+        .map(|(head, snake, _)| (snake.player, head.translation))
+        .collect();
+    if magnets.is_empty() {
+        return;
+    }
+    magnets.sort_by_key(|(player, _)| *player);
+    for mut item in &mut items {
+        let nearest = magnets
+            .iter()
+            .map(|(_, head)| *head)
+            .filter(|head| head.distance(item.translation) < MAGNET_RADIUS)
+            .min_by(|a, b| {
+                a.distance_squared(item.translation)
+                    .total_cmp(&b.distance_squared(item.translation))
+            });
+        let Some(head) = nearest else {
+            continue;
+        };
+        // only across the plane, items stay on their own layer
+        let mut pull = head - item.translation;
+        pull.z = 0.;
+        let step = MAGNET_PULL_SPEED * time.delta_seconds();
+        item.translation += pull.clamp_length_max(step);
+    }
+}
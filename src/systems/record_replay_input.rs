@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::{replay::ReplayRecorder, Game, Snake, SnakeControls};
+
+pub fn record_replay_input(
+    mut recorder: ResMut<ReplayRecorder>,
+    snakes: Query<(&Snake, &SnakeControls)>,
+    game: Res<Game>,
+) {
+    // replays only ever have the one player
+    if let Some((_, controls)) = snakes.iter().find(|(snake, _)| snake.player == 0) {
+        recorder.record_controls(game.ticks, controls);
+    }
+}
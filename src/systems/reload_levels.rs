@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::levels::{LevelCatalogue, LevelCatalogueHandle, Levels};
+
+/// Swaps in the levels file once the asset server has loaded it, and again each time it changes.
+/// A broken file is reported by the asset server and the levels left as they were.
+///
+/// A run that's already going carries on with the level as it was, the change shows up in the
+/// next one.
+pub fn reload_levels(
+    mut events: EventReader<AssetEvent<LevelCatalogue>>,
+    catalogues: Res<Assets<LevelCatalogue>>,
+    mut levels: ResMut<Levels>,
+    handle: Res<LevelCatalogueHandle>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = *event else {
+            continue;
+        };
+        if id != handle.0.id() {
+            continue;
+        }
+        if let Some(catalogue) = catalogues.get(id) {
+            info!("Loaded {} levels", catalogue.levels.len());
+            levels.replace(catalogue.clone());
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::{
+    upgrades::{UpgradeCatalogue, UpgradeCatalogueHandle},
+    Upgrades,
+};
+
+/// Swaps in the upgrade catalogue once the asset server has loaded it, and again each time the
+/// file changes, so prices and effects can be tuned without restarting the game.  A broken file is
+/// reported by the asset server and the upgrades left as they were.
+///
+/// The upgrades menu shows the new catalogue the next time it's opened.
+pub fn reload_upgrades(
+    mut events: EventReader<AssetEvent<UpgradeCatalogue>>,
+    catalogues: Res<Assets<UpgradeCatalogue>>,
+    mut upgrades: ResMut<Upgrades>,
+    handle: Res<UpgradeCatalogueHandle>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = *event else {
+            continue;
+        };
+        if id != handle.0.id() {
+            continue;
+        }
+        if let Some(catalogue) = catalogues.get(id) {
+            info!("Loaded {} upgrades", catalogue.upgrades.len());
+            upgrades.replace(catalogue.clone());
+        }
+    }
+}
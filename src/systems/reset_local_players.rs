@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+use crate::{GameMode, LocalPlayers};
+
+pub fn reset_local_players(mut players: ResMut<LocalPlayers>, mut mode: ResMut<GameMode>) {
+    *players = LocalPlayers::default();
+    *mode = GameMode::Classic;
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::{
+    levels::{Level, Levels},
+    replay::{Replay, ReplayPlayback, ReplayRecorder},
+    snake_bundle, AbilityCooldowns, AppState, CoinBag, Food, Game, GameRng, HungerRate,
+    LocalPlayers, RestartEvent, Rival, Rivals, RoundResults, Snake, SnakeMaxHealth, SnakeTailNode,
+    StatusEffects, Upgrades, SNAKE_HEAD_RADIUS, SPAWN_ATTEMPTS,
+};
+
+// visual layers
+const PLAYER_LAYER: f32 = 0.;
+
+pub fn restart(
+    mut ev_restart: EventReader<RestartEvent>,
+    mut commands: Commands,
+    mut game: ResMut<Game>,
+    mut next_state: ResMut<NextState<AppState>>,
+    to_despawn: Query<Entity, Or<(With<Snake>, With<SnakeTailNode>, With<Food>, With<CoinBag>)>>,
+    players: Res<LocalPlayers>,
+    mut rivals: ResMut<Rivals>,
+    levels: Res<Levels>,
+    mut level: ResMut<Level>,
+    mut rng: ResMut<GameRng>,
+    mut hunger_rate: ResMut<HungerRate>,
+    mut upgrades: ResMut<Upgrades>,
+    mut results: ResMut<RoundResults>,
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<ResMut<ReplayPlayback>>,
+) {
+    if !ev_restart.is_empty() {
+        ev_restart.clear();
+        // every run starts from exactly the same state, so that its seed and inputs are all it
+        // takes to reproduce it
+        rng.new_run();
+        info!("Restarting game with seed {}", rng.seed());
+        game.restart();
+        *hunger_rate = HungerRate::default();
+        upgrades.reset_levels();
+        *results = RoundResults::default();
+        let mut level_id = levels.selected;
+        if let Some(mut playback) = playback {
+            playback.rewind();
+            // the rivals and level are part of the run, so they have to be the same ones as last
+            // time
+            *rivals = playback.replay.rivals;
+            level_id = playback.replay.level;
+        }
+        if levels.by_id(level_id).is_none() {
+            warn!("There's no level {level_id}, playing the first one instead");
+        }
+        *level = levels.by_id_or_first(level_id).clone();
+        info!("Playing {}", level.name);
+        recorder.replay = Replay::new(rng.seed(), *rivals, level.id);
+        for entity in &to_despawn {
+            commands.entity(entity).despawn_recursive();
+        }
+        // rivals come after the players, so runs without them draw the same numbers as before
+        let humans = players.devices.len();
+        for player in 0..humans + rivals.count {
+            let mut snake_head_location = Vec2::ZERO;
+            if let Some(start) = level.snake_starts.get(player) {
+                snake_head_location = *start;
+            } else {
+                // if it can't find anywhere clear, it takes its chances with the last spot it tried
+                for _ in 0..SPAWN_ATTEMPTS {
+                    snake_head_location = level.random_point(&[], SNAKE_HEAD_RADIUS, &mut *rng);
+                    if level.clear_of_obstacles(snake_head_location, SNAKE_HEAD_RADIUS) {
+                        break;
+                    }
+                }
+            }
+            let snake_head_location = snake_head_location.extend(PLAYER_LAYER);
+            let mut snake = commands.spawn(snake_bundle(
+                Snake::new(player, SnakeMaxHealth::default().0),
+                snake_head_location,
+                Vec3::ZERO,
+                AbilityCooldowns::default(),
+                StatusEffects::default(),
+            ));
+            if player >= humans {
+                snake.insert(Rival(rivals.difficulty));
+            }
+        }
+
+        next_state.set(AppState::Playing);
+    }
+}
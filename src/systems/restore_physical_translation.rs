@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::PhysicalTranslation;
+
+// undoes any interpolation from the last frame, so the tick starts from where the last one ended
+pub fn restore_physical_translation(
+    mut physical: Query<(&mut Transform, &mut PhysicalTranslation)>,
+) {
+    for (mut transform, mut physical) in &mut physical {
+        transform.translation = physical.current;
+        physical.previous = physical.current;
+    }
+}
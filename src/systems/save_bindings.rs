@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::actions::InputBindings;
+
+pub fn save_bindings(bindings: Res<InputBindings>) {
+    // the new controls still work for this session, they just won't be remembered
+    if let Err(e) = bindings.save() {
+        warn!("Could not save controls: {e}");
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    replay::ReplayRecorder,
+    savegame::{
+        SaveGame, SavedBody, SavedCoinBag, SavedFood, SavedSnake, SavedTailNode, SAVEGAME_VERSION,
+    },
+    AbilityCooldowns, Awareness, CoinBag, Food, Game, GameRng, HungerRate, PhysicalTranslation,
+    Rival, SaveAndQuitEvent, Snake, SnakeControls, SnakeMaxHealth, SnakeSpeed, SnakeTailNode,
+    StatusEffects, TailNodeCount, TailOf, Velocity,
+};
+
+pub fn save_game(
+    mut ev_save_and_quit: EventReader<SaveAndQuitEvent>,
+    mut ev_exit: EventWriter<AppExit>,
+    game: Res<Game>,
+    rng: Res<GameRng>,
+    recorder: Res<ReplayRecorder>,
+    hunger_rate: Res<HungerRate>,
+    snakes: Query<(
+        Entity,
+        &Snake,
+        &PhysicalTranslation,
+        &Velocity,
+        &SnakeSpeed,
+        &SnakeMaxHealth,
+        &SnakeControls,
+        &AbilityCooldowns,
+        &StatusEffects,
+        Has<Rival>,
+    )>,
+    tail: Query<(
+        &SnakeTailNode,
+        &PhysicalTranslation,
+        &TailNodeCount,
+        &TailOf,
+    )>,
+    food: Query<(
+        &PhysicalTranslation,
+        &Velocity,
+        &Food,
+        &Transform,
+        &Awareness,
+    )>,
+    coin_bags: Query<(&CoinBag, &Transform)>,
+) {
+    if ev_save_and_quit.is_empty() {
+        return;
+    }
+    ev_save_and_quit.clear();
+    // saves only hold one player, the pause menu says as much
+    let mut players = snakes.iter().filter(|(.., rival)| !rival);
+    let (Some(player), None) = (players.next(), players.next()) else {
+        warn!("Only single player runs can be saved");
+        return;
+    };
+
+    // the simulation only ever sees `current`, whatever is being drawn in between ticks
+    let save_snake = |(
+        entity,
+        snake,
+        head,
+        velocity,
+        speed,
+        max_health,
+        controls,
+        cooldowns,
+        status_effects,
+        _,
+    ): (
+        Entity,
+        &Snake,
+        &PhysicalTranslation,
+        &Velocity,
+        &SnakeSpeed,
+        &SnakeMaxHealth,
+        &SnakeControls,
+        &AbilityCooldowns,
+        &StatusEffects,
+        bool,
+    )| {
+        let mut tail: Vec<_> = tail
+            .iter()
+            .filter(|(.., TailOf(owner))| *owner == entity)
+            .collect();
+        tail.sort_by_key(|(_, _, count, _)| count.0);
+        SavedSnake {
+            player: snake.player,
+            head: SavedBody {
+                translation: head.current.into(),
+                velocity: velocity.0.into(),
+            },
+            health: snake.health,
+            max_health: max_health.0,
+            speed: (speed.analog, speed.discrete),
+            score: snake.score,
+            controls: controls.clone(),
+            cooldowns: cooldowns.clone(),
+            status_effects: status_effects.clone(),
+            tail: tail
+                .into_iter()
+                .map(|(node, translation, ..)| SavedTailNode {
+                    translation: translation.current.into(),
+                    ignore_collision: node.0,
+                })
+                .collect(),
+        }
+    };
+    let save = SaveGame {
+        version: SAVEGAME_VERSION,
+        snake: save_snake(player),
+        rivals: snakes
+            .iter()
+            .filter(|(.., rival)| *rival)
+            .map(save_snake)
+            .collect(),
+        score: game.score,
+        coins: game.coins,
+        ticks: game.ticks,
+        upgrades: game.upgrades.clone(),
+        hunger_rate: hunger_rate.0,
+        food: food
+            .iter()
+            .map(
+                |(translation, velocity, food, transform, awareness)| SavedFood {
+                    translation: translation.current.into(),
+                    velocity: velocity.0.into(),
+                    kind: food.kind,
+                    spawned: food.spawned,
+                    rotation: transform.rotation.to_array(),
+                    awareness: awareness.level,
+                    alerted: awareness.alerted,
+                },
+            )
+            .collect(),
+        coin_bags: coin_bags
+            .iter()
+            .map(|(coin_bag, transform)| SavedCoinBag {
+                translation: transform.translation.into(),
+                value: coin_bag.value,
+            })
+            .collect(),
+        rng: rng.state().clone(),
+        seed: rng.seed(),
+        replay: recorder.replay.clone(),
+    };
+    // losing the run would be worse than not quitting, so stay in the pause menu if this fails
+    match save.save() {
+        Ok(()) => {
+            info!("Saved game after {} ticks", game.ticks);
+            ev_exit.send_default();
+        }
+        Err(e) => warn!("Could not save game: {e}"),
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::{highscores::unix_now, replay::ReplayRecorder, storage};
+
+// in the data directory, next to the high scores
+const REPLAY_DIRECTORY: &str = "replays";
+
+pub fn save_replay(recorder: Res<ReplayRecorder>) {
+    let path = storage::data_dir().join(REPLAY_DIRECTORY).join(format!(
+        "{}-{}.ron",
+        unix_now(),
+        recorder.replay.seed
+    ));
+    // a replay is nice to have, so failing to write one shouldn't take the game down
+    match recorder.replay.save(&path) {
+        Ok(()) => info!("Saved replay to {}", path.display()),
+        Err(e) => warn!("Could not save replay to {}: {e}", path.display()),
+    }
+}
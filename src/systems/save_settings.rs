@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+
+pub fn save_settings(settings: Res<Settings>) {
+    // the settings still apply for this session, they just won't be remembered
+    if let Err(e) = settings.save() {
+        warn!("Could not save settings: {e}");
+    }
+}
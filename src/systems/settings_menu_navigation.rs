@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState},
+    levels::Levels,
+    settings::{Settings, SettingsEntry},
+    AppState, SettingsMenuRow, SettingsMenuSelectedEntry,
+};
+
+pub fn settings_menu_navigation(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut actions: ResMut<ActionState>,
+    mut selected_entry: ResMut<SettingsMenuSelectedEntry>,
+    mut settings: ResMut<Settings>,
+    levels: Res<Levels>,
+    interactions: Query<(&Interaction, &SettingsMenuRow), Changed<Interaction>>,
+) {
+    let entries = SettingsEntry::ALL;
+    let mut index = entries
+        .iter()
+        .position(|entry| *entry == selected_entry.0)
+        .unwrap_or(0);
+    let mut steps = 0;
+    let mut confirmed = false;
+    let mut back = false;
+
+    for (interaction, SettingsMenuRow(entry)) in &interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                selected_entry.0 = *entry;
+                index = entries
+                    .iter()
+                    .position(|candidate| candidate == entry)
+                    .unwrap_or(0);
+                confirmed = true;
+            }
+            Interaction::Hovered => {
+                selected_entry.0 = *entry;
+                index = entries
+                    .iter()
+                    .position(|candidate| candidate == entry)
+                    .unwrap_or(0);
+            }
+            Interaction::None => {}
+        }
+    }
+
+    if actions.clear_just_pressed(Action::Back) {
+        back = true;
+    }
+    if actions.clear_just_pressed(Action::MenuUp) {
+        index = (index + entries.len() - 1) % entries.len();
+    }
+    if actions.clear_just_pressed(Action::MenuDown) {
+        index = (index + 1) % entries.len();
+    }
+    if actions.clear_just_pressed(Action::MenuLeft) {
+        steps -= 1;
+    }
+    if actions.clear_just_pressed(Action::MenuRight) {
+        steps += 1;
+    }
+    if actions.clear_just_pressed(Action::Confirm) {
+        confirmed = true;
+    }
+
+    selected_entry.0 = entries[index];
+    // confirming steps the value forwards, so everything can be changed with just a mouse
+    if confirmed {
+        match selected_entry.0 {
+            SettingsEntry::Controls => next_state.set(AppState::Controls),
+            SettingsEntry::Back => back = true,
+            _ => steps += 1,
+        }
+    }
+    if steps != 0 {
+        if selected_entry.0 == SettingsEntry::Level {
+            settings.level = levels.step(settings.level, steps);
+        } else {
+            settings.adjust(selected_entry.0, steps);
+        }
+    }
+    if back {
+        next_state.set(AppState::Paused);
+    }
+}
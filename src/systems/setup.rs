@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+pub fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
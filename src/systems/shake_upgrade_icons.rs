@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::UpgradeIconShake;
+
+const SHAKE_SECONDS: f32 = 0.3;
+// how far, in pixels, an icon moves either way at the start of a shake
+const SHAKE_DISTANCE: f32 = 8.;
+// how fast it goes back and forth, in radians per second
+const SHAKE_SPEED: f32 = 60.;
+
+pub fn shake_upgrade_icons(
+    mut commands: Commands,
+    mut icons: Query<(Entity, &mut UpgradeIconShake, &mut Style)>,
+    time: Res<Time>,
+) {
+    for (icon_entity, mut shake, mut style) in &mut icons {
+        shake.0 += time.delta_seconds();
+        if shake.0 >= SHAKE_SECONDS {
+            style.left = Val::Auto;
+            commands.entity(icon_entity).remove::<UpgradeIconShake>();
+            continue;
+        }
+        let fade = 1. - shake.0 / SHAKE_SECONDS;
+        style.left = Val::Px((shake.0 * SHAKE_SPEED).sin() * SHAKE_DISTANCE * fade);
+    }
+}
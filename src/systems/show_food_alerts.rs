@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{Awareness, FoodAlert};
+
+// how far above a mouse its "?" or "!" floats
+const ALERT_OFFSET: f32 = 35.;
+// in front of everything on the field
+const ALERT_LAYER: f32 = 1.;
+
+/// Floats a "?" over mice that are getting suspicious and a "!" over ones that are running, and
+/// cleans up after ones that have been eaten
+pub fn show_food_alerts(
+    mut commands: Commands,
+    mut alerts: Query<(
+        Entity,
+        &FoodAlert,
+        &mut Text,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+    food: Query<(&Transform, &Awareness), Without<FoodAlert>>,
+) {
+    for (alert_entity, FoodAlert(food_entity), mut text, mut transform, mut visibility) in
+        &mut alerts
+    {
+        let Ok((food_transform, awareness)) = food.get(*food_entity) else {
+            commands.entity(alert_entity).despawn();
+            continue;
+        };
+        let (mark, color) = if awareness.alerted {
+            ("!", Color::RED)
+        } else if awareness.suspicious() {
+            ("?", Color::YELLOW)
+        } else {
+            ("", Color::NONE)
+        };
+        *visibility = if mark.is_empty() {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        };
+        if text.sections[0].value != mark {
+            text.sections[0].value = mark.to_string();
+            text.sections[0].style.color = color;
+        }
+        transform.translation = food_transform.translation + Vec3::new(0., ALERT_OFFSET, 0.);
+        transform.translation.z = ALERT_LAYER;
+    }
+}
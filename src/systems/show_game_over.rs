@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::{AppState, GameOverEvent};
+
+pub fn show_game_over(
+    mut ev_game_over: EventReader<GameOverEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !ev_game_over.is_empty() {
+        ev_game_over.clear();
+        next_state.set(AppState::GameOver);
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::{
+    IconHoverEffectMaterial, PurchaseFeedbackText, PurchaseOutcome, UpgradeIcon, UpgradeIconShake,
+    UpgradePurchaseEvent, Upgrades,
+};
+
+/// Says whether buying an upgrade worked, flashing its focus ring green if it did, and red and
+/// shaking its icon if it didn't
+pub fn show_purchase_feedback(
+    mut commands: Commands,
+    mut ev_purchase: EventReader<UpgradePurchaseEvent>,
+    upgrades: Res<Upgrades>,
+    icons: Query<(Entity, &UpgradeIcon)>,
+    mut feedback: Query<&mut Text, With<PurchaseFeedbackText>>,
+    mut materials: ResMut<Assets<IconHoverEffectMaterial>>,
+) {
+    for ev in ev_purchase.read() {
+        let Some(upgrade) = upgrades.by_id(ev.upgrade_id) else {
+            continue;
+        };
+        let (message, color) = match ev.outcome {
+            PurchaseOutcome::Bought => (
+                format!("Bought {}, now {}", upgrade.name(), upgrade.level_text()),
+                Color::GREEN,
+            ),
+            PurchaseOutcome::TooExpensive => (
+                format!("Not enough coins for {}", upgrade.name()),
+                Color::RED,
+            ),
+            PurchaseOutcome::MaxedOut => (
+                format!("{} is already at its max level", upgrade.name()),
+                Color::RED,
+            ),
+        };
+        for (_, material) in materials.iter_mut() {
+            if material.upgrade_id == ev.upgrade_id {
+                material.color = color.into();
+            }
+        }
+        for mut text in &mut feedback {
+            text.sections[0].value = message.clone();
+            text.sections[0].style.color = color;
+        }
+        if ev.outcome != PurchaseOutcome::Bought {
+            for (icon_entity, icon) in &icons {
+                if icon.upgrade_id == ev.upgrade_id {
+                    commands
+                        .entity(icon_entity)
+                        .insert(UpgradeIconShake::default());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::{AbilityOutput, SECONDARY_FONT_NAME};
+
+pub fn spawn_ability_output(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                margin: UiRect::all(Val::Px(15.)),
+                position_type: PositionType::Absolute,
+                left: Val::Px(15.),
+                bottom: Val::Px(15.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load(SECONDARY_FONT_NAME),
+                        font_size: 24.0,
+                        ..default()
+                    },
+                ),
+                AbilityOutput,
+            ));
+        });
+}
@@ -1,41 +1,27 @@
 use bevy::prelude::*;
 
-use crate::{
-    FOOD_LAYER,
-    CoinBag,
-    RandNormalized,
-};
+use crate::{coin_bag_bundle, levels::Level, GameRng, FOOD_LAYER, FOOD_RADIUS, SPAWN_ATTEMPTS};
 
 use rand::Rng;
 
-// the minimum distance from the edge of the screen that coins spawn at
+// the minimum distance from the edge of the game field that coins spawn at, on levels that don't
+// say where they go
 const COIN_BOUNDARY: f32 = 128.;
 
-pub fn spawn_coins(mut commands: Commands, asset_server: Res<AssetServer>, window: Query<&Window>) {
-    let mut coins_location = Vec3::random();
-    let window = window.single();
-    let boundary_x = (window.resolution.width() / 2.) - COIN_BOUNDARY;
-    let boundary_y = (window.resolution.height() / 2.) - COIN_BOUNDARY;
+// chance per tick of a bag appearing while there isn't one on the field
+const COIN_SPAWN_CHANCE: f32 = 0.02;
 
-    coins_location.x -= 0.5;
-    coins_location.y -= 0.5;
-
-    coins_location.x *= boundary_x * 2.;
-    coins_location.y *= boundary_y * 2.;
-
-    coins_location.z = FOOD_LAYER;
-    commands.spawn((
-        CoinBag {
-            value: (rand::thread_rng().gen_range(8.0..12.0f32) * 100.0).round() / 100.0,
-        },
-        SpriteBundle {
-            texture: asset_server.load("sprites/coinbag.png"),
-            transform: Transform {
-                translation: coins_location,
-                scale: Vec3::new(0.3, 0.3, 1.0),
-                ..default()
-            },
-            ..default()
-        },
-    ));
+pub fn spawn_coins(mut commands: Commands, level: Res<Level>, mut rng: ResMut<GameRng>) {
+    if !rng.chance(COIN_SPAWN_CHANCE) {
+        return;
+    }
+    for _ in 0..SPAWN_ATTEMPTS {
+        let coins_location = level.random_point(&level.coin_zones, COIN_BOUNDARY, &mut *rng);
+        if level.clear_of_obstacles(coins_location, FOOD_RADIUS) {
+            let coins_location = coins_location.extend(FOOD_LAYER);
+            let value = (rng.gen_range(8.0..12.0f32) * 100.0).round() / 100.0;
+            commands.spawn(coin_bag_bundle(coins_location, value));
+            return;
+        }
+    }
 }
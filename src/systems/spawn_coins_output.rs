@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::{CoinbagValueOutput, CoinsOutput, Game, SECONDARY_FONT_NAME};
+
+pub fn spawn_coins_output(mut commands: Commands, game: Res<Game>, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                margin: UiRect::all(Val::Px(15.)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                right: Val::Px(15.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("Coins: {0}", game.coins),
+                    TextStyle {
+                        font: asset_server.load(SECONDARY_FONT_NAME),
+                        font_size: 32.0,
+                        ..default()
+                    },
+                ),
+                CoinsOutput,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    "Bag Value",
+                    TextStyle {
+                        font: asset_server.load(SECONDARY_FONT_NAME),
+                        font_size: 32.0,
+                        ..default()
+                    },
+                ),
+                CoinbagValueOutput,
+            ));
+        });
+}
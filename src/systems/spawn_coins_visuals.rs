@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::CoinBag;
+
+pub fn spawn_coins_visuals(
+    mut commands: Commands,
+    coins: Query<(Entity, &Transform), Added<CoinBag>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (coins, transform) in &coins {
+        commands.entity(coins).insert(SpriteBundle {
+            texture: asset_server.load("sprites/coinbag.png"),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
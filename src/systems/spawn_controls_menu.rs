@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::{
+    ControlsEntry, ControlsMenu, ControlsMenuRow, ControlsMenuState, PRIMARY_FONT_NAME,
+    SECONDARY_FONT_NAME,
+};
+
+pub fn spawn_controls_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut menu_state: ResMut<ControlsMenuState>,
+) {
+    *menu_state = ControlsMenuState::default();
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            ControlsMenu,
+        ))
+        .with_children(|parent| {
+            // container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Controls",
+                        TextStyle {
+                            font: asset_server.load(PRIMARY_FONT_NAME),
+                            font_size: 72.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                    for entry in ControlsEntry::all() {
+                        // the text is filled in by `update_controls_menu`
+                        parent
+                            .spawn((crate::get_button(), ControlsMenuRow(entry)))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "",
+                                    TextStyle {
+                                        font: asset_server.load(SECONDARY_FONT_NAME),
+                                        font_size: 20.0,
+                                        color: Color::WHITE,
+                                    },
+                                ));
+                            });
+                    }
+                });
+        });
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::{DebugOutput, SECONDARY_FONT_NAME};
+
+pub fn spawn_debug_output(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load(SECONDARY_FONT_NAME);
+    let mut text_bundle = TextBundle::from_sections([
+        TextSection::from_style(TextStyle {
+            font: font.clone(),
+            font_size: 20.0,
+            color: Color::FUCHSIA,
+        }),
+        TextSection::from_style(TextStyle {
+            font: font.clone(),
+            font_size: 20.0,
+            color: Color::FUCHSIA,
+        }),
+    ])
+    .with_style(Style {
+        flex_direction: FlexDirection::Column,
+        top: Val::Px(75.0),
+        left: Val::Px(15.0),
+        ..default()
+    });
+    text_bundle.visibility = Visibility::Hidden;
+    commands.spawn((text_bundle, DebugOutput));
+}
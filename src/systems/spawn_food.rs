@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::{
+    food::{FoodKind, MAX_FOOD},
+    food_bundle,
+    levels::Level,
+    Awareness, Food, Game, GameRng, FOOD_LAYER, SIMULATION_HZ, SPAWN_ATTEMPTS,
+};
+
+// chance each tick of more food turning up while there's already some about
+const EXTRA_FOOD_CHANCE: f32 = 1. / (8. * SIMULATION_HZ as f32);
+
+/// Makes sure there's always something to eat, and every so often adds more, up to [`MAX_FOOD`]
+pub fn spawn_food(
+    mut commands: Commands,
+    food: Query<(), With<Food>>,
+    level: Res<Level>,
+    game: Res<Game>,
+    mut rng: ResMut<GameRng>,
+) {
+    let food_count = food.iter().count();
+    if food_count >= MAX_FOOD || (food_count > 0 && !rng.chance(EXTRA_FOOD_CHANCE)) {
+        return;
+    }
+    let kind = FoodKind::pick(&mut *rng);
+    let radius = kind.stats().radius;
+    for _ in 0..SPAWN_ATTEMPTS {
+        let food_location = level.random_point(&level.food_zones, radius, &mut *rng);
+        if level.clear_of_obstacles(food_location, radius) {
+            let food_location = food_location.extend(FOOD_LAYER);
+            commands.spawn(food_bundle(
+                kind,
+                game.ticks,
+                food_location,
+                Vec3::ZERO,
+                Quat::IDENTITY,
+                Awareness::default(),
+            ));
+            return;
+        }
+    }
+}
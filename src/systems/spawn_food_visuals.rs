@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use crate::{Food, FoodAlert, PRIMARY_FONT_NAME};
+
+pub fn spawn_food_visuals(
+    mut commands: Commands,
+    food: Query<(Entity, &Transform, &Food), Added<Food>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (food, transform, Food { kind, .. }) in &food {
+        let stats = kind.stats();
+        commands.entity(food).insert(SpriteBundle {
+            texture: asset_server.load(stats.sprite),
+            sprite: Sprite {
+                color: stats.tint,
+                ..default()
+            },
+            transform: *transform,
+            ..default()
+        });
+        // kept separate from the mouse, so it doesn't turn and shrink with it
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load(PRIMARY_FONT_NAME),
+                        font_size: 40.,
+                        ..default()
+                    },
+                ),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            FoodAlert(food),
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use crate::{levels::Level, GameField, GameFieldMaterial};
+
+// redrawn whenever the level changes, since each one has its own size and background
+pub fn spawn_game_field_quad(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<GameFieldMaterial>>,
+    old_game_field: Query<Entity, With<GameField>>,
+    level: Res<Level>,
+    asset_server: Res<AssetServer>,
+) {
+    for game_field in &old_game_field {
+        commands.entity(game_field).despawn();
+    }
+    let gamefield_size = level.field_size;
+
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(shape::Quad::new(gamefield_size).into()).into(),
+            material: materials.add(GameFieldMaterial {
+                width: gamefield_size.x,
+                height: gamefield_size.y,
+                texture: asset_server.load(&level.background),
+            }),
+            transform: Transform::from_translation(Vec3::new(0., 0., -1.)),
+            ..default()
+        },
+        GameField,
+    ));
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+
+use crate::{
+    get_button, highscores::HighScores, player_color, GameMode, GameOver, QuitButton,
+    RestartButton, RoundResults, BUTTON_FONT_SIZE, PRIMARY_FONT_NAME, SECONDARY_FONT_NAME,
+};
+
+pub fn spawn_game_over_splash(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+    results: Res<RoundResults>,
+    mode: Res<GameMode>,
+) {
+    // rivals end up in the results too, but a classic run still goes in the high score table
+    let multiplayer = *mode != GameMode::Classic;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            GameOver,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            if results.level_complete {
+                                "Level Complete"
+                            } else {
+                                "Game Over"
+                            },
+                            TextStyle {
+                                font: asset_server.load(PRIMARY_FONT_NAME),
+                                font_size: 72.0,
+                                ..default()
+                            },
+                        )
+                        .with_style(Style { ..default() }),
+                        Label, // a11y tag
+                    ));
+                    // everyone's result in local multiplayer, otherwise the high score table
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                margin: UiRect::vertical(Val::Px(20.)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            if multiplayer {
+                                for (rank, result) in results.ranked().into_iter().enumerate() {
+                                    let cause = result
+                                        .cause
+                                        .map_or_else(|| "survived".to_string(), |cause| cause.to_string());
+                                    parent.spawn((
+                                        TextBundle::from_section(
+                                            format!(
+                                                "{}. P{}  {:>6}  tail {:<3}  {}",
+                                                rank + 1,
+                                                result.player + 1,
+                                                result.score,
+                                                result.tail_length,
+                                                cause,
+                                            ),
+                                            TextStyle {
+                                                font: asset_server.load(SECONDARY_FONT_NAME),
+                                                font_size: 24.0,
+                                                color: player_color(result.player),
+                                            },
+                                        ),
+                                        Label,
+                                    ));
+                                }
+                                return;
+                            }
+                            for (rank, entry) in high_scores.entries().iter().enumerate() {
+                                // point out where the run that just ended placed
+                                let color = if high_scores.last_rank() == Some(rank) {
+                                    Color::YELLOW
+                                } else {
+                                    Color::rgb(0.9, 0.9, 0.9)
+                                };
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        format!(
+                                            "{:>2}. {:>6}  {}  tail {:<3}  {:<2} upgrades  {}  seed {}",
+                                            rank + 1,
+                                            entry.score,
+                                            entry.date_string(),
+                                            entry.tail_length,
+                                            entry.upgrades.len(),
+                                            entry.mode,
+                                            entry.seed,
+                                        ),
+                                        TextStyle {
+                                            font: asset_server.load(SECONDARY_FONT_NAME),
+                                            font_size: 20.0,
+                                            color,
+                                        },
+                                    ),
+                                    Label,
+                                ));
+                            }
+                        });
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn((get_button(), RestartButton, GameOver))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Restart",
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: Color::rgb(0.9, 0.9, 0.9),
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn((get_button(), QuitButton, GameOver))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Quit",
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: Color::rgb(0.9, 0.9, 0.9),
+                                        },
+                                    ));
+                                });
+                        });
+                });
+        });
+    // 3. handle "restart" and "quit" button presses
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::{levels::Level, obstacle_bundle, Obstacle};
+
+/// Puts the obstacles of the level being played in place of the last level's.  Runs whenever a
+/// run starts or continues on a level
+pub fn spawn_level(
+    mut commands: Commands,
+    level: Res<Level>,
+    obstacles: Query<Entity, With<Obstacle>>,
+) {
+    for obstacle in &obstacles {
+        commands.entity(obstacle).despawn_recursive();
+    }
+    for placement in &level.obstacles {
+        commands.spawn(obstacle_bundle(*placement));
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::{GameMode, LobbyMenu, LobbyText, LocalPlayers, PRIMARY_FONT_NAME, SECONDARY_FONT_NAME};
+
+pub fn spawn_lobby(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut players: ResMut<LocalPlayers>,
+    mut mode: ResMut<GameMode>,
+) {
+    // everyone joins from scratch, in the order they press confirm
+    players.devices.clear();
+    *mode = GameMode::Versus;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            LobbyMenu,
+        ))
+        .with_children(|parent| {
+            // container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Local Multiplayer",
+                        TextStyle {
+                            font: asset_server.load(PRIMARY_FONT_NAME),
+                            font_size: 72.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                    // filled in by `update_lobby`
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: asset_server.load(SECONDARY_FONT_NAME),
+                                font_size: 24.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        LobbyText,
+                    ));
+                });
+        });
+}
@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+use crate::{
+    savegame::SaveGame, ContinueButton, MainMenu, MainMenuButton, MainMenuSelectedButton,
+    MultiplayerButton, PlayButton, QuitButton, BUTTON_FONT_SIZE, PRIMARY_FONT_NAME,
+};
+
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut selected_button: ResMut<MainMenuSelectedButton>,
+) {
+    // a run left over from last time is most likely what the player wants to get back to
+    let can_continue = SaveGame::exists();
+    *selected_button = if can_continue {
+        MainMenuSelectedButton::Continue
+    } else {
+        MainMenuSelectedButton::Play
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            MainMenu,
+        ))
+        .with_children(|parent| {
+            // container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Snake",
+                        TextStyle {
+                            font: asset_server.load(PRIMARY_FONT_NAME),
+                            font_size: 96.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            if can_continue {
+                                parent
+                                    .spawn((
+                                        crate::get_button(),
+                                        ContinueButton,
+                                        MainMenuButton(MainMenuSelectedButton::Continue),
+                                        MainMenu,
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            "Continue",
+                                            TextStyle {
+                                                font: asset_server.load(PRIMARY_FONT_NAME),
+                                                font_size: BUTTON_FONT_SIZE,
+                                                color: Color::WHITE,
+                                            },
+                                        ));
+                                    });
+                            }
+                            parent
+                                .spawn((
+                                    crate::get_button(),
+                                    PlayButton,
+                                    MainMenuButton(MainMenuSelectedButton::Play),
+                                    MainMenu,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Play",
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: Color::WHITE,
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn((
+                                    crate::get_button(),
+                                    MultiplayerButton,
+                                    MainMenuButton(MainMenuSelectedButton::Multiplayer),
+                                    MainMenu,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Local Multiplayer",
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: Color::WHITE,
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn((
+                                    crate::get_button(),
+                                    QuitButton,
+                                    MainMenuButton(MainMenuSelectedButton::Quit),
+                                    MainMenu,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Quit",
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: Color::WHITE,
+                                        },
+                                    ));
+                                });
+                        });
+                });
+        });
+}
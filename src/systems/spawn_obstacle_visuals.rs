@@ -0,0 +1,37 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use crate::{obstacles::ObstacleShape, Obstacle};
+
+pub fn spawn_obstacle_visuals(
+    mut commands: Commands,
+    obstacles: Query<(Entity, &Transform, &Obstacle), Added<Obstacle>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (obstacle_entity, transform, obstacle) in &obstacles {
+        let color = obstacle.kind.color();
+        match obstacle.shape {
+            ObstacleShape::Circle { radius } => {
+                commands
+                    .entity(obstacle_entity)
+                    .insert(MaterialMesh2dBundle {
+                        mesh: meshes.add(shape::Circle::new(radius).into()).into(),
+                        material: color_materials.add(ColorMaterial::from(color)),
+                        transform: *transform,
+                        ..default()
+                    });
+            }
+            ObstacleShape::Rect { half_size } => {
+                commands.entity(obstacle_entity).insert(SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(half_size * 2.),
+                        ..default()
+                    },
+                    transform: *transform,
+                    ..default()
+                });
+            }
+        }
+    }
+}
@@ -1,9 +1,25 @@
-use bevy::prelude::{AlignItems, Color, Commands, default, FlexDirection, JustifyContent, NodeBundle, Res, Style, TextBundle, TextStyle, Val, Visibility};
+use crate::{
+    LocalPlayers, PauseMenu, PauseMenuButton, PauseMenuSelectedButton, QuitButton,
+    SaveAndQuitButton, SettingsButton, UpgradesButton, BUTTON_FONT_SIZE, PRIMARY_FONT_NAME,
+};
 use bevy::asset::AssetServer;
 use bevy::hierarchy::BuildChildren;
-use crate::{BUTTON_FONT_SIZE, PauseMenu, PRIMARY_FONT_NAME, QuitButton, UpgradesButton};
+use bevy::prelude::{
+    default, AlignItems, Color, Commands, FlexDirection, JustifyContent, NodeBundle, Res, Style,
+    TextBundle, TextStyle, Val,
+};
 
-pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    players: Res<LocalPlayers>,
+) {
+    // a save only has room for one player, so say so rather than have the button do nothing
+    let (save_label, save_color) = if players.devices.len() == 1 {
+        ("Save & Quit", Color::WHITE)
+    } else {
+        ("Save & Quit (single player only)", Color::GRAY)
+    };
     commands
         .spawn((
             NodeBundle {
@@ -14,7 +30,6 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                visibility: Visibility::Hidden,
                 ..default()
             },
             PauseMenu,
@@ -51,7 +66,12 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                         })
                         .with_children(|parent| {
                             parent
-                                .spawn((crate::get_button(), UpgradesButton, PauseMenu))
+                                .spawn((
+                                    crate::get_button(),
+                                    UpgradesButton,
+                                    PauseMenuButton(PauseMenuSelectedButton::Upgrades),
+                                    PauseMenu,
+                                ))
                                 .with_children(|parent| {
                                     parent.spawn(TextBundle::from_section(
                                         "Upgrades",
@@ -63,7 +83,46 @@ pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                                     ));
                                 });
                             parent
-                                .spawn((crate::get_button(), QuitButton, PauseMenu))
+                                .spawn((
+                                    crate::get_button(),
+                                    SettingsButton,
+                                    PauseMenuButton(PauseMenuSelectedButton::Settings),
+                                    PauseMenu,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Settings",
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: Color::WHITE,
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn((
+                                    crate::get_button(),
+                                    SaveAndQuitButton,
+                                    PauseMenuButton(PauseMenuSelectedButton::SaveAndQuit),
+                                    PauseMenu,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        save_label,
+                                        TextStyle {
+                                            font: asset_server.load(PRIMARY_FONT_NAME),
+                                            font_size: BUTTON_FONT_SIZE,
+                                            color: save_color,
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn((
+                                    crate::get_button(),
+                                    QuitButton,
+                                    PauseMenuButton(PauseMenuSelectedButton::Quit),
+                                    PauseMenu,
+                                ))
                                 .with_children(|parent| {
                                     parent.spawn(TextBundle::from_section(
                                         "Quit",
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+use crate::{
+    Game, ScoreOutput, StatusEffect, StatusEffectBar, StatusEffectIcon, StatusEffectLabel,
+    SECONDARY_FONT_NAME,
+};
+
+const STATUS_ICON_SIZE: f32 = 40.;
+
+pub fn spawn_score_output(mut commands: Commands, game: Res<Game>, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                margin: UiRect::all(Val::Px(15.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("Score: {0}", game.score),
+                    TextStyle {
+                        font: asset_server.load(SECONDARY_FONT_NAME),
+                        font_size: 32.0,
+                        ..default()
+                    },
+                ),
+                ScoreOutput,
+            ));
+            // hidden until the effect is on
+            for effect in StatusEffect::ALL {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                display: Display::None,
+                                flex_direction: FlexDirection::Column,
+                                margin: UiRect::left(Val::Px(10.)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        StatusEffectIcon(effect),
+                    ))
+                    .with_children(|parent| {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Px(STATUS_ICON_SIZE),
+                                    height: Val::Px(STATUS_ICON_SIZE),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: effect.color().into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        effect.label(),
+                                        TextStyle {
+                                            font: asset_server.load(SECONDARY_FONT_NAME),
+                                            font_size: 14.0,
+                                            color: Color::BLACK,
+                                        },
+                                    )
+                                    .with_text_alignment(TextAlignment::Center),
+                                    StatusEffectLabel(effect),
+                                ));
+                            });
+                        // how long it has left
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Px(STATUS_ICON_SIZE),
+                                    height: Val::Px(6.),
+                                    margin: UiRect::top(Val::Px(2.)),
+                                    ..default()
+                                },
+                                background_color: Color::DARK_GRAY.into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            width: Val::Percent(100.),
+                                            height: Val::Percent(100.),
+                                            ..default()
+                                        },
+                                        background_color: effect.color().into(),
+                                        ..default()
+                                    },
+                                    StatusEffectBar(effect),
+                                ));
+                            });
+                    });
+            }
+        });
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::{
+    settings::SettingsEntry, SettingsMenu, SettingsMenuRow, SettingsMenuSelectedEntry,
+    BUTTON_FONT_SIZE, PRIMARY_FONT_NAME,
+};
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut selected_entry: ResMut<SettingsMenuSelectedEntry>,
+) {
+    selected_entry.0 = SettingsEntry::ALL[0];
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            SettingsMenu,
+        ))
+        .with_children(|parent| {
+            // container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Settings",
+                        TextStyle {
+                            font: asset_server.load(PRIMARY_FONT_NAME),
+                            font_size: 72.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                    for entry in SettingsEntry::ALL {
+                        // the text is filled in by `update_settings_menu`
+                        parent
+                            .spawn((crate::get_button(), SettingsMenuRow(entry)))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    entry.label(),
+                                    TextStyle {
+                                        font: asset_server.load(PRIMARY_FONT_NAME),
+                                        font_size: BUTTON_FONT_SIZE,
+                                        color: Color::WHITE,
+                                    },
+                                ));
+                            });
+                    }
+                });
+        });
+}
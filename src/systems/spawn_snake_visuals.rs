@@ -0,0 +1,38 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use crate::{player_color, HealthbarMaterial, Snake, SNAKE_HEAD_RADIUS};
+
+pub fn spawn_snake_visuals(
+    mut commands: Commands,
+    snakes: Query<(Entity, &Transform, &Snake), Added<Snake>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut healthbar_materials: ResMut<Assets<HealthbarMaterial>>,
+) {
+    for (snake_entity, transform, snake) in &snakes {
+        commands
+            .entity(snake_entity)
+            .insert(MaterialMesh2dBundle {
+                mesh: meshes
+                    .add(shape::Circle::new(SNAKE_HEAD_RADIUS).into())
+                    .into(),
+                material: color_materials.add(ColorMaterial::from(player_color(snake.player))),
+                transform: *transform,
+                ..default()
+            })
+            .with_children(|parent| {
+                // snake health bar
+                parent.spawn(MaterialMesh2dBundle {
+                    mesh: meshes
+                        .add(shape::Quad::new(Vec2::new(SNAKE_HEAD_RADIUS * 2.0, 10.)).into())
+                        .into(),
+                    material: healthbar_materials.add(HealthbarMaterial { health: 1. }),
+                    transform: Transform {
+                        translation: Vec3::new(0., SNAKE_HEAD_RADIUS + 20., 1.),
+                        ..default()
+                    },
+                    ..default()
+                });
+            });
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use crate::{player_color, Snake, SnakeTailNode, TailOf, SNAKE_HEAD_RADIUS};
+
+pub fn spawn_tail_node_visuals(
+    mut commands: Commands,
+    tail_nodes: Query<(Entity, &Transform, &TailOf), Added<SnakeTailNode>>,
+    snakes: Query<&Snake>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (tail_node, transform, TailOf(owner)) in &tail_nodes {
+        let player = snakes.get(*owner).map_or(0, |snake| snake.player);
+        commands.entity(tail_node).insert(MaterialMesh2dBundle {
+            mesh: meshes
+                .add(shape::Circle::new(SNAKE_HEAD_RADIUS).into())
+                .into(),
+            material: materials.add(ColorMaterial::from(player_color(player))),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+
+use crate::{
+    IconHoverEffectMaterial, PurchaseFeedbackText, UpgradeDetailText, UpgradeDetailTitle,
+    UpgradeIcon, UpgradeIconImage, UpgradeLevelText, UpgradePriceText, Upgrades, UpgradesMenu,
+    PRIMARY_FONT_NAME, UPGRADE_GRID_COLUMNS,
+};
+
+pub fn spawn_upgrades_menu(
+    mut commands: Commands,
+    upgrades: Res<Upgrades>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<IconHoverEffectMaterial>>,
+) {
+    let text_style = |font_size| TextStyle {
+        font: asset_server.load(PRIMARY_FONT_NAME),
+        font_size,
+        ..default()
+    };
+    commands
+        .spawn((
+            UpgradesMenu,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    padding: UiRect::px(50., 50., 200., 0.),
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0., 0., 0., 0.7)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::FlexStart,
+                        display: Display::Grid,
+                        grid_template_columns: RepeatedGridTrack::auto(UPGRADE_GRID_COLUMNS as u16),
+                        justify_content: JustifyContent::FlexStart,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_upgrade_icons(parent, &upgrades, &asset_server, &mut materials);
+                });
+            // everything about whichever upgrade is selected
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(500.),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(16.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        UpgradeDetailTitle,
+                        TextBundle::from_section("", text_style(40.)),
+                    ));
+                    parent.spawn((
+                        UpgradeDetailText,
+                        TextBundle::from_section("", text_style(24.)),
+                    ));
+                    parent.spawn((
+                        PurchaseFeedbackText,
+                        TextBundle::from_section("", text_style(24.)),
+                    ));
+                });
+        });
+}
+
+pub fn spawn_upgrade_icons(
+    parent: &mut ChildBuilder,
+    upgrades: &Upgrades,
+    asset_server: &AssetServer,
+    materials: &mut Assets<IconHoverEffectMaterial>,
+) {
+    for upgrade in &upgrades.upgrades {
+        parent
+            .spawn((
+                UpgradeIcon {
+                    upgrade_id: upgrade.id,
+                },
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(64.),
+                        height: Val::Px(64.),
+                        margin: UiRect::all(Val::Px(32.)),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::rgba(0., 0., 0., 0.7)),
+                    ..default()
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    UpgradeIconImage {
+                        upgrade_id: upgrade.id,
+                    },
+                    ImageBundle {
+                        image: UiImage::new(
+                            asset_server.load(format!("upgrades/{0}", upgrade.icon)),
+                        ),
+                        style: Style { ..default() },
+                        ..default()
+                    },
+                ));
+                parent.spawn(MaterialNodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(64.),
+                        height: Val::Px(64.),
+                        ..default()
+                    },
+                    material: materials.add(IconHoverEffectMaterial {
+                        upgrade_id: upgrade.id,
+                        color: Color::WHITE.into(),
+                        highlight: 0,
+                    }),
+                    ..default()
+                });
+                parent.spawn(TextBundle::from_section(
+                    upgrade.name.clone(),
+                    TextStyle {
+                        font: asset_server.load(PRIMARY_FONT_NAME),
+                        font_size: 32.,
+                        ..default()
+                    },
+                ));
+                parent.spawn((
+                    UpgradeLevelText {
+                        upgrade_id: upgrade.id,
+                    },
+                    TextBundle::from_section(
+                        upgrade.level_text(),
+                        TextStyle {
+                            font: asset_server.load(PRIMARY_FONT_NAME),
+                            font_size: 16.,
+                            ..default()
+                        },
+                    ),
+                ));
+                parent.spawn((
+                    UpgradePriceText {
+                        upgrade_id: upgrade.id,
+                    },
+                    TextBundle::from_section(
+                        upgrade.price_text(),
+                        TextStyle {
+                            font: asset_server.load(PRIMARY_FONT_NAME),
+                            font_size: 16.,
+                            ..default()
+                        },
+                    ),
+                ));
+            });
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::{
+    levels::Level, CoinBag, Food, Game, Obstacle, Rival, Snake, SnakeControls, SnakeTailNode,
+    TailOf, SELF_COLLISION_MIN_TAIL, SNAKE_HEAD_RADIUS,
+};
+
+// how far away the most cautious rival starts steering away from walls and bodies
+const MAX_AVOID_DISTANCE: f32 = 250.;
+
+// how much more steering away from danger matters than heading for the target
+const AVOID_WEIGHT: f32 = 2.;
+
+/// Decides where each rival wants to go: towards the nearest food (and coins, if it's aggressive
+/// enough), and away from walls, obstacles, poisoned food and anything it could crash into.
+///
+/// Only reads the simulation, never the rng, so that rivals behave the same in replays.
+pub fn steer_rivals(
+    mut rivals: Query<(Entity, &Transform, &Snake, &Rival, &mut SnakeControls)>,
+    heads: Query<(Entity, &Transform), With<Snake>>,
+    tail_nodes: Query<(&Transform, &SnakeTailNode, &TailOf)>,
+    food: Query<(&Transform, &Food)>,
+    coins: Query<&Transform, With<CoinBag>>,
+    obstacles: Query<(&Transform, &Obstacle)>,
+    level: Res<Level>,
+    game: Res<Game>,
+) {
+    let boundary = level.field_size / 2. - SNAKE_HEAD_RADIUS;
+    for (rival_entity, head, snake, Rival(difficulty), mut controls) in &mut rivals {
+        // rivals only change their mind every so often, staggered so they don't all turn at once
+        if (game.ticks + snake.player as u64) % difficulty.reaction_ticks() != 0 {
+            continue;
+        }
+        let aggression = difficulty.aggression();
+        let position = head.translation.truncate();
+
+        let mut targets: Vec<Vec2> = food
+            .iter()
+            .filter(|(_, food)| !food.kind.harmful())
+            .map(|(transform, _)| transform.translation.truncate())
+            .collect();
+        if aggression >= 0.5 {
+            targets.extend(coins.iter().map(|coins| coins.translation.truncate()));
+        }
+        let seek = targets
+            .into_iter()
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+            .map_or(Vec2::ZERO, |target| (target - position).normalize_or_zero());
+
+        // anything closer than this pushes the rival away, harder the closer it is
+        let avoid_distance = SNAKE_HEAD_RADIUS * 2. + MAX_AVOID_DISTANCE * (1. - aggression);
+        let mut avoid = Vec2::ZERO;
+        let mut push_away_from = |obstacle: Vec2| {
+            let away = position - obstacle;
+            let distance = away.length();
+            if distance < avoid_distance {
+                avoid += away.normalize_or_zero() * (1. - distance / avoid_distance);
+            }
+        };
+        let own_tail_length = tail_nodes
+            .iter()
+            .filter(|(_, _, TailOf(owner))| *owner == rival_entity)
+            .count();
+        for (tail_node, SnakeTailNode(ignore_collision), TailOf(owner)) in &tail_nodes {
+            // same rules as `collide_with_tails`, so it doesn't dodge its own neck
+            let own_tail = *owner == rival_entity;
+            if own_tail && (*ignore_collision || own_tail_length < SELF_COLLISION_MIN_TAIL) {
+                continue;
+            }
+            push_away_from(tail_node.translation.truncate());
+        }
+        for (transform, food) in &food {
+            if food.kind.harmful() {
+                push_away_from(transform.translation.truncate());
+            }
+        }
+        for (other, other_head) in &heads {
+            if other != rival_entity {
+                push_away_from(other_head.translation.truncate());
+            }
+        }
+        for (transform, obstacle) in &obstacles {
+            let center = transform.translation.truncate();
+            push_away_from(obstacle.shape.closest_point(center, position));
+        }
+        // walls
+        push_away_from(Vec2::new(boundary.x, position.y));
+        push_away_from(Vec2::new(-boundary.x, position.y));
+        push_away_from(Vec2::new(position.x, boundary.y));
+        push_away_from(Vec2::new(position.x, -boundary.y));
+
+        controls.direction = seek + avoid * AVOID_WEIGHT;
+        controls.stick = Vec2::ZERO;
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+use crate::PhysicalTranslation;
+
+pub fn store_physical_translation(mut physical: Query<(&Transform, &mut PhysicalTranslation)>) {
+    for (transform, mut physical) in &mut physical {
+        physical.current = transform.translation;
+    }
+}
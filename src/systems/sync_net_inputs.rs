@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::{net::NetSession, Game, Snake, SnakeControls};
+
+/// Swaps inputs with the other player, and steers both snakes with the ones due this tick.  If
+/// either hasn't arrived yet, the tick waits
+pub fn sync_net_inputs(
+    mut session: ResMut<NetSession>,
+    mut snakes: Query<(&Snake, &mut SnakeControls)>,
+    game: Res<Game>,
+) {
+    session.poll();
+    session.exchange_inputs(game.ticks);
+    let Some((local, remote)) = session.inputs_for(game.ticks) else {
+        return;
+    };
+    let local_player = session.local_player();
+    for (snake, mut controls) in &mut snakes {
+        if snake.player == local_player {
+            local.apply(&mut controls);
+        } else {
+            remote.apply(&mut controls);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*};
+
+use crate::{
+    actions::InputDevice,
+    levels::Levels,
+    net::{NetEvent, NetPhase, NetRole, NetSession},
+    AppState, GameMode, GameRng, LocalPlayers, RestartEvent, Rivals,
+};
+
+/// Looks after the online match between ticks: joining, starting rounds, and ending them when
+/// the simulations disagree or the other player leaves.
+///
+/// Only the host can start a round.  The client's own restarts are dropped, and it restarts
+/// when the host tells it to instead, with the host's seed, rivals and level.
+pub fn sync_net_session(
+    mut commands: Commands,
+    mut session: ResMut<NetSession>,
+    mut restarts: ResMut<Events<RestartEvent>>,
+    mut restarts_seen: Local<ManualEventReader<RestartEvent>>,
+    mut rng: ResMut<GameRng>,
+    mut rivals: ResMut<Rivals>,
+    mut levels: ResMut<Levels>,
+    mut players: ResMut<LocalPlayers>,
+    mut mode: ResMut<GameMode>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    session.poll();
+    session.resend(*rivals, levels.selected);
+
+    let restart_requested = restarts_seen.read(&restarts).count() > 0;
+    if restart_requested {
+        match (session.role(), session.phase()) {
+            (NetRole::Host, NetPhase::Connected) => {
+                *rng = GameRng::new(session.start_round(*rivals, levels.selected));
+            }
+            (NetRole::Host, _) => {
+                info!("Waiting for someone to join before starting");
+                restarts.clear();
+            }
+            (NetRole::Client, _) => {
+                info!("Waiting for the host to start the round");
+                restarts.clear();
+            }
+        }
+    }
+
+    for event in session.take_events() {
+        match event {
+            NetEvent::Connected => {
+                let mut devices = vec![InputDevice::Any, InputDevice::Network];
+                if session.role() == NetRole::Client {
+                    devices.reverse();
+                }
+                *players = LocalPlayers { devices };
+                *mode = GameMode::Versus;
+                if session.role() == NetRole::Host {
+                    *rng = GameRng::new(session.start_round(*rivals, levels.selected));
+                    restarts.send(RestartEvent);
+                }
+            }
+            NetEvent::StartRound {
+                seed,
+                rivals: host_rivals,
+                level,
+            } => {
+                *rng = GameRng::new(seed);
+                *rivals = host_rivals;
+                levels.selected = level;
+                restarts.send(RestartEvent);
+            }
+            NetEvent::Desync { .. } => {
+                if *state.get() == AppState::Playing {
+                    next_state.set(AppState::GameOver);
+                }
+            }
+            NetEvent::Disconnected => {
+                commands.remove_resource::<NetSession>();
+                *players = LocalPlayers::default();
+                *mode = GameMode::Classic;
+                if *state.get() == AppState::Playing {
+                    next_state.set(AppState::GameOver);
+                }
+            }
+        }
+    }
+    // the restarts sent above aren't the player's, so they mustn't be dropped next frame
+    restarts_seen.read(&restarts).for_each(drop);
+}
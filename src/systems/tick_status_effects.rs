@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::StatusEffects;
+
+/// Counts down every snake's status effects, and takes off the ones that have worn off
+pub fn tick_status_effects(mut status_effects: Query<&mut StatusEffects>) {
+    for mut status_effects in &mut status_effects {
+        status_effects.tick();
+    }
+}
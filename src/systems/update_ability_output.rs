@@ -0,0 +1,46 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::{
+    actions::InputBindings, Ability, AbilityCooldowns, AbilityOutput, Rival, Snake, Upgrades,
+    SIMULATION_HZ,
+};
+
+/// Lists the abilities bought so far, with their buttons and how long until each player can use
+/// them again
+pub fn update_ability_output(
+    mut ability_text: Query<&mut Text, With<AbilityOutput>>,
+    snakes: Query<(&Snake, &AbilityCooldowns), Without<Rival>>,
+    upgrades: Res<Upgrades>,
+    bindings: Res<InputBindings>,
+) {
+    let mut snakes: Vec<_> = snakes.iter().collect();
+    snakes.sort_by_key(|(snake, _)| snake.player);
+    let mut output = String::new();
+    for ability in Ability::ALL {
+        if !upgrades.unlocked(ability) {
+            continue;
+        }
+        let _ = write!(output, "{ability}");
+        if let Some(binding) = bindings.get(ability.action()).first() {
+            let _ = write!(output, " [{binding}]");
+        }
+        for (snake, cooldowns) in &snakes {
+            if snakes.len() > 1 {
+                let _ = write!(output, "  P{}", snake.player + 1);
+            }
+            if cooldowns.ready(ability) {
+                let _ = write!(output, " ready");
+            } else {
+                let seconds = cooldowns.remaining(ability) as f64 / SIMULATION_HZ;
+                let _ = write!(output, " {seconds:.1}s");
+            }
+        }
+        output.push('\n');
+    }
+    let mut ability_text = ability_text.single_mut();
+    if ability_text.sections[0].value != output {
+        ability_text.sections[0].value = output;
+    }
+}
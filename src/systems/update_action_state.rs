@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::actions::{ActionState, InputBindings, RawInput};
+
+pub fn update_action_state(
+    mut actions: ResMut<ActionState>,
+    bindings: Res<InputBindings>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    button_axes: Res<Axis<GamepadButton>>,
+) {
+    let input = RawInput {
+        keys: &keys,
+        gamepads: &gamepads,
+        buttons: &buttons,
+        button_axes: &button_axes,
+    };
+    actions.update(&bindings, &input);
+}
@@ -1,14 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{
-    CoinbagValueOutput,
-    CoinsOutput,
-    CoinBag,
-    Game,
-};
+use crate::{CoinBag, CoinbagValueOutput, CoinsOutput, Game};
 
 pub fn update_coins_output(
-    mut coins_text: Query<&mut Text, With<CoinsOutput>>, 
+    mut coins_text: Query<&mut Text, With<CoinsOutput>>,
     mut bagvalue_text: Query<&mut Text, (With<CoinbagValueOutput>, Without<CoinsOutput>)>,
     bag: Query<&CoinBag>,
     game: Res<Game>,
@@ -17,7 +12,7 @@ pub fn update_coins_output(
     coins_text.sections[0].value = format!("Coins: {0:>12.2}", game.coins);
     let mut bagvalue_text = bagvalue_text.single_mut();
     bagvalue_text.sections[0].value = if bag.is_empty() {
-        format!("")
+        String::new()
     } else {
         let bag = bag.single();
         format!("Bag Value: {0:>8.2}", bag.value)
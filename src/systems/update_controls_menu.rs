@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::{actions::InputBindings, ControlsEntry, ControlsMenuRow, ControlsMenuState};
+
+pub fn update_controls_menu(
+    bindings: Res<InputBindings>,
+    menu_state: Res<ControlsMenuState>,
+    mut rows: Query<(&ControlsMenuRow, &mut Style, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    let selected = ControlsEntry::all().nth(menu_state.selected);
+    for (ControlsMenuRow(entry), mut style, children) in &mut rows {
+        let is_selected = Some(*entry) == selected;
+        if is_selected {
+            style.border.bottom = Val::Px(2.);
+            style.margin.bottom = Val::Px(-2.);
+        } else {
+            style.border.bottom = Val::ZERO;
+            style.margin.bottom = Val::ZERO;
+        }
+        let label = match entry {
+            ControlsEntry::Action(action) if is_selected && menu_state.waiting => {
+                format!(
+                    "{}: press a key or button (Escape to cancel)",
+                    action.label()
+                )
+            }
+            ControlsEntry::Action(action) => {
+                let bound: Vec<_> = bindings
+                    .get(*action)
+                    .iter()
+                    .map(|binding| binding.to_string())
+                    .collect();
+                format!("{}: {}", action.label(), bound.join(", "))
+            }
+            ControlsEntry::ResetDefaults => "Reset to Defaults".into(),
+            ControlsEntry::Back => "Back".into(),
+        };
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if text.sections[0].value != label {
+                    text.sections[0].value = label.clone();
+                }
+            }
+        }
+    }
+}
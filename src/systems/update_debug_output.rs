@@ -0,0 +1,36 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::{DebugOutput, Food, Game, Snake, SnakeTailNode, Upgrades, Velocity};
+
+pub fn update_debug_output(
+    mut texts: Query<&mut Text, With<DebugOutput>>,
+    snakes: Query<(&Transform, &Velocity, &Snake)>,
+    food: Query<(&Transform, &Velocity, &Food)>,
+    tail_nodes: Query<(), With<SnakeTailNode>>,
+    game: Res<Game>,
+    upgrades: Res<Upgrades>,
+) {
+    let mut text = texts.single_mut();
+    let tail_node_count = tail_nodes.iter().count();
+    let mut s = String::new();
+    for (food_location, food_velocity, Food { kind, .. }) in &food {
+        let food_location = food_location.translation;
+        let Velocity(food_velocity) = *food_velocity;
+        let _ = writeln!(s, "{kind:?} location: {food_location}");
+        let _ = writeln!(s, "{kind:?} velocity: {food_velocity}");
+    }
+    text.sections[1].value = s;
+    let mut s = String::new();
+    for (snake_transform, snake_velocity, snake) in &snakes {
+        let Velocity(velocity) = *snake_velocity;
+        let position = snake_transform.translation;
+        let _ = writeln!(s, "P{0} head velocity: {velocity}", snake.player + 1);
+        let _ = writeln!(s, "P{0} head position: {position}", snake.player + 1);
+    }
+    let _ = writeln!(s, "Snake tail sections: {tail_node_count}");
+    let _ = writeln!(s, "Score: {0}", game.score);
+    let _ = writeln!(s, "Upgrades selected index: {0}", upgrades.selected_index);
+    text.sections[0].value = s;
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::{Awareness, Food, Snake, Velocity};
+
+// a snake moving slower than this is sneaking, and barely gets noticed
+const SNEAK_SPEED: f32 = 200.;
+// how quickly the meter fills, per second, for a snake going at `SNEAK_SPEED` right in front of
+// a mouse's nose
+const FILL_RATE: f32 = 2.;
+// how quickly it empties again, per second, while nothing is bothering it
+const DRAIN_RATE: f32 = 0.5;
+// how much more a mouse notices what's in front of it than what's behind it
+const FRONT_BIAS: f32 = 0.5;
+// once it's running, it calms down when its meter gets back down to this
+const CALM_LEVEL: f32 = 0.3;
+
+/// Fills each mouse's awareness meter, faster the closer, faster and more in front of it a snake
+/// is, and empties it when they're still or creeping up slowly.  A full meter sends it running
+pub fn update_food_awareness(
+    time: Res<Time>,
+    mut food: Query<(&Transform, &Food, &mut Awareness)>,
+    snakes: Query<(&Transform, &Velocity), With<Snake>>,
+) {
+    let delta = time.delta_seconds();
+    for (food_transform, Food { kind, .. }, mut awareness) in &mut food {
+        let stats = kind.stats();
+        let position = food_transform.translation.truncate();
+        // the sprite faces up
+        let facing = (food_transform.rotation * Vec3::Y).truncate();
+        let noticed: f32 = snakes
+            .iter()
+            .map(|(head, Velocity(velocity))| {
+                let to_head = head.translation.truncate() - position;
+                let closeness = (1. - to_head.length() / stats.sight).max(0.);
+                let in_front = 1. + FRONT_BIAS * facing.dot(to_head.normalize_or_zero());
+                let speed = velocity.truncate().length() / SNEAK_SPEED;
+                closeness * closeness * in_front * speed
+            })
+            .sum();
+        let level = awareness.level + (noticed * FILL_RATE * stats.wariness - DRAIN_RATE) * delta;
+        awareness.level = level.clamp(0., 1.);
+        if awareness.level >= 1. {
+            awareness.alerted = true;
+        } else if awareness.level <= CALM_LEVEL {
+            awareness.alerted = false;
+        }
+    }
+}
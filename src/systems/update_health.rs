@@ -1,21 +1,24 @@
 use bevy::prelude::*;
 
-use crate::{
-    Snake,
-    GameOverEvent,
-    HungerRate,
-};
+use crate::{DeathCause, HungerRate, Snake, SnakeDiedEvent, StatusEffect, StatusEffects};
 
 pub fn update_health(
-    mut snake: Query<&mut Snake>,
+    mut snakes: Query<(Entity, &mut Snake, &StatusEffects)>,
     time: Res<Time>,
-    mut ev_game_over: EventWriter<GameOverEvent>,
+    mut ev_snake_died: EventWriter<SnakeDiedEvent>,
     hunger_rate: Res<HungerRate>,
 ) {
-    let mut snake = snake.single_mut();
     let HungerRate(hunger_rate) = *hunger_rate;
-    snake.health -= hunger_rate * time.delta_seconds();
-    if snake.health <= 0. {
-        ev_game_over.send_default();
+    for (entity, mut snake, status_effects) in &mut snakes {
+        if status_effects.has(StatusEffect::HungerFree) {
+            continue;
+        }
+        snake.health -= hunger_rate * time.delta_seconds();
+        if snake.health <= 0. {
+            ev_snake_died.send(SnakeDiedEvent {
+                snake: entity,
+                cause: DeathCause::Starved,
+            });
+        }
     }
 }
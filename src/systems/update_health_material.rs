@@ -1,16 +1,20 @@
 use bevy::prelude::*;
 
-use crate::{
-    HealthbarMaterial,
-    Snake,
-};
+use crate::{HealthbarMaterial, Snake};
 
 pub fn update_health_material(
     mut materials: ResMut<Assets<HealthbarMaterial>>,
-    snake: Query<&Snake>,
+    snakes: Query<(&Snake, &Children)>,
+    healthbars: Query<&Handle<HealthbarMaterial>>,
 ) {
-    let snake = snake.single();
-    for (_, material) in materials.iter_mut() {
-        material.health = snake.health / 100.0;
+    for (snake, children) in &snakes {
+        for child in children {
+            let Ok(healthbar) = healthbars.get(*child) else {
+                continue;
+            };
+            if let Some(material) = materials.get_mut(healthbar) {
+                material.health = snake.health / 100.0;
+            }
+        }
     }
 }
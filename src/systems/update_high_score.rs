@@ -1,12 +1,47 @@
 use bevy::prelude::*;
 
 use crate::{
-    HighScore,
-    Game,
+    highscores::{unix_now, HighScoreEntry, HighScores},
+    replay::ReplayPlayback,
+    Game, GameMode, GameRng, Rival, Snake, TailOf, Upgrades,
 };
 
-pub fn update_high_score(game: Res<Game>, high_score: Res<HighScore>) {
-    if game.score > high_score.get() {
-        high_score.save(game.score);
+pub fn update_high_score(
+    game: Res<Game>,
+    mut high_scores: ResMut<HighScores>,
+    rng: Res<GameRng>,
+    mode: Res<GameMode>,
+    upgrades: Res<Upgrades>,
+    tail: Query<&TailOf>,
+    player_snakes: Query<Entity, (With<Snake>, Without<Rival>)>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    // watching a replay isn't playing, so it doesn't get a place in the table, and local
+    // multiplayer has its own results screen instead
+    if playback.is_some() || *mode != GameMode::Classic {
+        return;
+    }
+    let entry = HighScoreEntry {
+        score: game.score,
+        date: unix_now(),
+        tail_length: tail
+            .iter()
+            .filter(|TailOf(owner)| player_snakes.contains(*owner))
+            .count(),
+        seed: rng.seed(),
+        mode: *mode,
+        upgrades: game
+            .upgrades
+            .iter()
+            .filter_map(|id| upgrades.by_id(*id))
+            .map(|upgrade| upgrade.name.clone())
+            .collect(),
+    };
+    if let Some(rank) = high_scores.record(entry) {
+        info!("New high score table entry at #{}", rank + 1);
+        // the table is still in memory, so the player only loses it on the next launch
+        if let Err(e) = high_scores.save() {
+            warn!("Could not save high scores: {e}");
+        }
     }
 }
@@ -0,0 +1,37 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::{player_color, GameMode, LobbyText, LocalPlayers, MAX_LOCAL_PLAYERS};
+
+pub fn update_lobby(
+    players: Res<LocalPlayers>,
+    mode: Res<GameMode>,
+    mut text: Query<&mut Text, With<LobbyText>>,
+) {
+    if !players.is_changed() && !mode.is_changed() {
+        return;
+    }
+    let mut text = text.single_mut();
+    let style = text.sections[0].style.clone();
+    let mut sections = Vec::new();
+    let mut header = String::new();
+    let _ = writeln!(header, "\nMode: < {} >\n", *mode);
+    sections.push(TextSection::new(header, style.clone()));
+    for (player, device) in players.devices.iter().enumerate() {
+        sections.push(TextSection::new(
+            format!("P{}: {device}\n", player + 1),
+            TextStyle {
+                color: player_color(player),
+                ..style.clone()
+            },
+        ));
+    }
+    let mut footer = String::new();
+    if players.devices.len() < MAX_LOCAL_PLAYERS {
+        let _ = writeln!(footer, "\nPress Confirm on a keyboard or gamepad to join");
+    }
+    let _ = writeln!(footer, "Press Back to leave, Pause to start");
+    sections.push(TextSection::new(footer, style));
+    text.sections = sections;
+}
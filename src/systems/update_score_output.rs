@@ -1,20 +1,27 @@
 use bevy::prelude::*;
 
-use crate::{
-    Game,
-    ScoreOutput,
-    HighScore,
-};
+use crate::{highscores::HighScores, Game, ScoreOutput, Snake};
 
 pub fn update_score_output(
     mut score_text: Query<&mut Text, With<ScoreOutput>>,
     game: Res<Game>,
-    high_score: Res<HighScore>,
+    high_scores: Res<HighScores>,
+    snakes: Query<&Snake>,
 ) {
     let mut score_text = score_text.single_mut();
-    score_text.sections[0].value = format!(
-        "Last High Score: {0}\nScore: {1}",
-        high_score.get(),
-        game.score
-    );
+    let mut snakes: Vec<_> = snakes.iter().collect();
+    score_text.sections[0].value = if snakes.len() > 1 {
+        snakes.sort_by_key(|snake| snake.player);
+        snakes
+            .iter()
+            .map(|snake| format!("P{0}: {1}", snake.player + 1, snake.score))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        format!(
+            "Last High Score: {0}\nScore: {1}",
+            high_scores.best(),
+            game.score
+        )
+    };
 }
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::{
+    levels::Levels,
+    settings::{Settings, SettingsEntry},
+    SettingsMenuRow, SettingsMenuSelectedEntry,
+};
+
+pub fn update_settings_menu(
+    settings: Res<Settings>,
+    levels: Res<Levels>,
+    selected_entry: Res<SettingsMenuSelectedEntry>,
+    mut rows: Query<(&SettingsMenuRow, &mut Style, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (SettingsMenuRow(entry), mut style, children) in &mut rows {
+        if *entry == selected_entry.0 {
+            style.border.bottom = Val::Px(2.);
+            style.margin.bottom = Val::Px(-2.);
+        } else {
+            style.border.bottom = Val::ZERO;
+            style.margin.bottom = Val::ZERO;
+        }
+        let label = match entry {
+            SettingsEntry::Controls | SettingsEntry::Back => entry.label().to_string(),
+            SettingsEntry::Level => {
+                let level = levels.by_id_or_first(settings.level);
+                format!("{}: {} ({})", entry.label(), level.name, level.goal)
+            }
+            _ => format!("{}: {}", entry.label(), settings.value_text(*entry)),
+        };
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if text.sections[0].value != label {
+                    text.sections[0].value = label.clone();
+                }
+            }
+        }
+    }
+}
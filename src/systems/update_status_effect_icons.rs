@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use crate::{
+    net::NetSession, Snake, StatusEffectBar, StatusEffectIcon, StatusEffectLabel, StatusEffects,
+};
+
+/// Shows an icon for each status effect on the local player's snake, with a bar for how long
+/// it has left
+pub fn update_status_effect_icons(
+    snakes: Query<(&Snake, &StatusEffects)>,
+    session: Option<Res<NetSession>>,
+    mut icons: Query<(&StatusEffectIcon, &mut Style), Without<StatusEffectBar>>,
+    mut labels: Query<(&StatusEffectLabel, &mut Text)>,
+    mut bars: Query<(&StatusEffectBar, &mut Style), Without<StatusEffectIcon>>,
+) {
+    // the first player's, or whoever is playing on this machine in an online match
+    let local_player = session.map_or(0, |session| session.local_player());
+    let status_effects = snakes
+        .iter()
+        .find(|(snake, _)| snake.player == local_player)
+        .map(|(_, status_effects)| status_effects);
+    let remaining = |effect| status_effects.and_then(|effects| effects.remaining_fraction(effect));
+
+    for (StatusEffectIcon(effect), mut style) in &mut icons {
+        let display = if remaining(*effect).is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+    for (StatusEffectLabel(effect), mut text) in &mut labels {
+        let stacks = status_effects.map_or(0, |effects| effects.stacks(*effect));
+        let label = if stacks > 1 {
+            format!("{}\nx{stacks}", effect.label())
+        } else {
+            effect.label().to_string()
+        };
+        if text.sections[0].value != label {
+            text.sections[0].value = label;
+        }
+    }
+    for (StatusEffectBar(effect), mut style) in &mut bars {
+        let width = Val::Percent(remaining(*effect).unwrap_or_default() * 100.);
+        if style.width != width {
+            style.width = width;
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::{
+    upgrades::UpgradeStats, Game, Rival, Snake, SnakeMaxHealth, SnakeSpeed, TailOf,
+    UpgradeDetailText, UpgradeDetailTitle, UpgradeIconImage, Upgrades,
+};
+
+// how dark the icons of upgrades that can't be bought right now are
+const UNAVAILABLE_TINT: Color = Color::rgb(0.35, 0.35, 0.35);
+
+/// Fills in the details panel for the selected upgrade, and greys out the ones that can't be bought
+pub fn update_upgrade_details(
+    upgrades: Res<Upgrades>,
+    game: Res<Game>,
+    snakes: Query<(Entity, &Snake, &SnakeSpeed, &SnakeMaxHealth), Without<Rival>>,
+    tail_nodes: Query<&TailOf>,
+    mut titles: Query<&mut Text, (With<UpgradeDetailTitle>, Without<UpgradeDetailText>)>,
+    mut details: Query<&mut Text, (With<UpgradeDetailText>, Without<UpgradeDetailTitle>)>,
+    mut icons: Query<(&UpgradeIconImage, &mut BackgroundColor)>,
+) {
+    for (UpgradeIconImage { upgrade_id }, mut background) in &mut icons {
+        let available = upgrades
+            .by_id(*upgrade_id)
+            .is_some_and(|upgrade| upgrade.can_buy(game.coins));
+        let tint = if available {
+            Color::WHITE
+        } else {
+            UNAVAILABLE_TINT
+        };
+        if background.0 != tint {
+            background.0 = tint;
+        }
+    }
+
+    let Some(upgrade) = upgrades.upgrades.get(upgrades.selected_index) else {
+        return;
+    };
+    // previews the first player's stats, everyone else's change just the same
+    let stats = snakes
+        .iter()
+        .find(|(_, snake, ..)| snake.player == 0)
+        .map_or_else(
+            || UpgradeStats {
+                speed: SnakeSpeed::default().analog,
+                max_health: SnakeMaxHealth::default().0,
+                tail_length: 0,
+            },
+            |(snake_entity, _, speed, max_health)| UpgradeStats {
+                speed: speed.analog,
+                max_health: max_health.0,
+                tail_length: tail_nodes
+                    .iter()
+                    .filter(|TailOf(owner)| *owner == snake_entity)
+                    .count(),
+            },
+        );
+
+    let mut detail = format!("{}\n{}\n", upgrade.level_text(), upgrade.description());
+    for effect in upgrade.effects() {
+        let Some((stat, now, next)) = effect.preview(&stats) else {
+            continue;
+        };
+        if upgrade.maxed_out() {
+            let _ = write!(detail, "\n{stat} {now:.0}");
+        } else {
+            let _ = write!(detail, "\n{stat} {now:.0} -> {next:.0}");
+        }
+    }
+    let _ = write!(
+        detail,
+        "\n\n{}, you have {:.0}",
+        upgrade.price_text(),
+        game.coins
+    );
+
+    for mut title in &mut titles {
+        if title.sections[0].value != upgrade.name() {
+            title.sections[0].value = upgrade.name().to_string();
+        }
+    }
+    for mut text in &mut details {
+        if text.sections[0].value != detail {
+            text.sections[0].value = detail.clone();
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::{UpgradeLevelText, UpgradePriceText, Upgrades};
+
+/// Keeps the levels and prices in the upgrades menu up to date as upgrades are bought
+pub fn update_upgrade_labels(
+    upgrades: Res<Upgrades>,
+    mut level_texts: Query<(&UpgradeLevelText, &mut Text), Without<UpgradePriceText>>,
+    mut price_texts: Query<(&UpgradePriceText, &mut Text), Without<UpgradeLevelText>>,
+) {
+    for (UpgradeLevelText { upgrade_id }, mut text) in &mut level_texts {
+        if let Some(upgrade) = upgrades.by_id(*upgrade_id) {
+            text.sections[0].value = upgrade.level_text();
+        }
+    }
+    for (UpgradePriceText { upgrade_id }, mut text) in &mut price_texts {
+        if let Some(upgrade) = upgrades.by_id(*upgrade_id) {
+            text.sections[0].value = upgrade.price_text();
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState},
+    IconHoverEffectMaterial, UpgradeIcon, UpgradeIconClickedEvent, Upgrades, UPGRADE_GRID_COLUMNS,
+};
+
+/// Moves the focus around the upgrades grid with the menu directions, buys the focused upgrade on
+/// Confirm, and rings whichever one has the focus
+pub fn upgrades_menu_navigation(
+    mut actions: ResMut<ActionState>,
+    mut upgrades: ResMut<Upgrades>,
+    mut ev_upgrade_icon_clicked: EventWriter<UpgradeIconClickedEvent>,
+    mut materials: ResMut<Assets<IconHoverEffectMaterial>>,
+) {
+    let count = upgrades.upgrades.len();
+    if count == 0 {
+        return;
+    }
+    let index = upgrades.selected_index;
+    let column = index % UPGRADE_GRID_COLUMNS;
+    let mut next = index;
+    // left and right stay on the row, up and down stay in the column
+    if actions.clear_just_pressed(Action::MenuLeft) && column > 0 {
+        next -= 1;
+    }
+    if actions.clear_just_pressed(Action::MenuRight)
+        && column + 1 < UPGRADE_GRID_COLUMNS
+        && index + 1 < count
+    {
+        next += 1;
+    }
+    if actions.clear_just_pressed(Action::MenuUp) && index >= UPGRADE_GRID_COLUMNS {
+        next -= UPGRADE_GRID_COLUMNS;
+    }
+    // going down from a row above a short last row lands on the last upgrade
+    if actions.clear_just_pressed(Action::MenuDown)
+        && index / UPGRADE_GRID_COLUMNS < (count - 1) / UPGRADE_GRID_COLUMNS
+    {
+        next = (index + UPGRADE_GRID_COLUMNS).min(count - 1);
+    }
+    if next != index {
+        upgrades.selected_index = next;
+    }
+
+    let Some(selected) = upgrades.upgrades.get(upgrades.selected_index) else {
+        return;
+    };
+    let selected_id = selected.id;
+    if actions.clear_just_pressed(Action::Confirm) {
+        ev_upgrade_icon_clicked.send(UpgradeIconClickedEvent {
+            icon: UpgradeIcon {
+                upgrade_id: selected_id,
+            },
+        });
+    }
+
+    // only touch materials that change, so they aren't all re-uploaded every frame
+    let ids: Vec<_> = materials
+        .iter()
+        .filter(|(_, material)| material.highlight != u32::from(material.upgrade_id == selected_id))
+        .map(|(id, _)| id)
+        .collect();
+    for id in ids {
+        if let Some(material) = materials.get_mut(id) {
+            let focused = material.upgrade_id == selected_id;
+            material.highlight = u32::from(focused);
+            // a purchase flashes the ring, which fades back to normal once the focus moves on
+            if !focused {
+                material.color = Color::WHITE.into();
+            }
+        }
+    }
+}
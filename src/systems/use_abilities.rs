@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{
+    Ability, AbilityCooldowns, Snake, SnakeControls, SnakeTailNode, TailNodeCount, TailOf,
+    Upgrades, Velocity, SIMULATION_HZ, SNAKE_HEAD_RADIUS,
+};
+
+// how many times faster a dash makes the snake go
+const DASH_MULTIPLIER: f32 = 2.5;
+// a dashing head never moves more than its own radius in one tick, so it can't skip over a tail
+// node without touching it
+const MAX_DASH_SPEED: f32 = SNAKE_HEAD_RADIUS * SIMULATION_HZ as f32;
+
+/// Uses the ability each snake asked for, if it's been bought and has cooled down.
+///
+/// Runs before `move_snake`, so a dash or brake changes this tick's movement, and wall deaths are
+/// judged on where the snake ends up afterwards.
+pub fn use_abilities(
+    mut commands: Commands,
+    mut snakes: Query<
+        (
+            Entity,
+            &mut Velocity,
+            &mut SnakeControls,
+            &mut AbilityCooldowns,
+        ),
+        With<Snake>,
+    >,
+    tail_nodes: Query<(Entity, &TailOf, &TailNodeCount), With<SnakeTailNode>>,
+    upgrades: Res<Upgrades>,
+) {
+    for (snake, mut velocity, mut controls, mut cooldowns) in &mut snakes {
+        cooldowns.tick();
+        let Some(ability) = controls.ability.take() else {
+            continue;
+        };
+        if !upgrades.unlocked(ability) || !cooldowns.ready(ability) {
+            continue;
+        }
+        let Velocity(ref mut velocity) = *velocity;
+        match ability {
+            Ability::Dash => {
+                *velocity = (*velocity * DASH_MULTIPLIER).clamp_length_max(MAX_DASH_SPEED);
+            }
+            Ability::Brake => *velocity = Vec3::ZERO,
+            Ability::ShedTail => {
+                let tail_node_count = tail_nodes
+                    .iter()
+                    .filter(|(_, TailOf(owner), _)| *owner == snake)
+                    .count();
+                // nothing to shed, so don't waste the cooldown
+                if tail_node_count == 0 {
+                    continue;
+                }
+                let kept = tail_node_count / 2;
+                for (tail_node, TailOf(owner), TailNodeCount(index)) in &tail_nodes {
+                    if *owner == snake && *index >= kept {
+                        commands.entity(tail_node).despawn();
+                    }
+                }
+            }
+        }
+        cooldowns.start(ability);
+    }
+}
@@ -0,0 +1,268 @@
+//! The upgrades for sale in the upgrades menu, declared in `assets/game.upgrades.ron` rather than
+//! in code, so that prices and effects can be tuned without a rebuild.
+//!
+//! The game loads the file as an asset when it starts, and again whenever it changes while the
+//! game is running.  A copy of it is built into the game too, for headless tools, which run
+//! without an asset server.
+
+use std::{collections::HashSet, error::Error, fmt, io};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::{Ability, Upgrade};
+
+const BUILT_IN_CATALOGUE: &[u8] = include_bytes!("../assets/game.upgrades.ron");
+// where the icons are, in the assets folder
+const ICON_FOLDER: &str = "upgrades";
+
+/// What buying an upgrade does.  Upgrades are bought from the shared pot, so they affect every
+/// snake
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub enum UpgradeEffect {
+    /// Faster, by this much with a stick and with the arrow keys
+    Speed { analog: f32, discrete: f32 },
+    /// More max health, so it takes longer to starve
+    MaxHealth(f32),
+    /// Cuts each player's tail down to this fraction of its length
+    SplitTail(f32),
+    /// Lets every player use an ability, with its own button
+    UnlockAbility(Ability),
+}
+
+/// The stats upgrades change, as they are right now, for previewing what buying one would do
+pub struct UpgradeStats {
+    pub speed: f32,
+    pub max_health: f32,
+    pub tail_length: usize,
+}
+
+impl UpgradeEffect {
+    /// The stat this changes, what it is now, and what it would be after buying it.  `None` if
+    /// it doesn't change a stat
+    pub fn preview(&self, stats: &UpgradeStats) -> Option<(&'static str, f32, f32)> {
+        let preview = match *self {
+            Self::Speed { analog, .. } => ("Speed", stats.speed, stats.speed + analog),
+            Self::MaxHealth(amount) => ("Max health", stats.max_health, stats.max_health + amount),
+            // same sum as the split itself, which keeps the node at the cut as well
+            Self::SplitTail(keep) => {
+                let kept = ((stats.tail_length as f32 * keep) as usize + 1).min(stats.tail_length);
+                ("Tail length", stats.tail_length as f32, kept as f32)
+            }
+            Self::UnlockAbility(_) => return None,
+        };
+        Some(preview)
+    }
+}
+
+/// How an upgrade's price goes up each time it's bought
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PriceCurve {
+    /// Always costs the same
+    #[default]
+    Fixed,
+    /// Costs this much more each time
+    Linear(f32),
+    /// Costs this many times as much each time, rounded to whole coins
+    Exponential(f32),
+}
+
+impl PriceCurve {
+    /// What it costs once it's been bought `level` times, starting from `base`
+    pub fn price(self, base: f32, level: usize) -> f32 {
+        match self {
+            Self::Fixed => base,
+            Self::Linear(step) => base + step * level as f32,
+            Self::Exponential(factor) => (base * factor.powi(level as i32)).round(),
+        }
+    }
+}
+
+/// Everything for sale, as read from the catalogue file
+#[derive(Asset, TypePath, Deserialize, Clone)]
+pub struct UpgradeCatalogue {
+    pub upgrades: Vec<Upgrade>,
+}
+
+#[derive(Debug)]
+pub enum CatalogueError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for CatalogueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl Error for CatalogueError {}
+
+impl UpgradeCatalogue {
+    /// Where the asset server finds the catalogue, in the assets folder
+    pub const PATH: &'static str = "game.upgrades.ron";
+
+    pub fn parse(catalogue: &[u8]) -> Result<Self, CatalogueError> {
+        let catalogue: Self = ron::de::from_bytes(catalogue).map_err(CatalogueError::Parse)?;
+        catalogue.validate()?;
+        Ok(catalogue)
+    }
+
+    /// The catalogue the game was built with
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_CATALOGUE).expect("the built-in upgrade catalogue is broken")
+    }
+    // saves and replays refer to upgrades by id, so two with the same one would be ambiguous
+    fn validate(&self) -> Result<(), CatalogueError> {
+        let mut ids = HashSet::new();
+        for upgrade in &self.upgrades {
+            let invalid = |reason: &str| {
+                Err(CatalogueError::Invalid(format!(
+                    "upgrade {} ({}) {reason}",
+                    upgrade.id, upgrade.name
+                )))
+            };
+            if !ids.insert(upgrade.id) {
+                return invalid("has the same id as another upgrade");
+            }
+            if !upgrade.price.is_finite() || upgrade.price < 0. {
+                return invalid("has a negative price");
+            }
+            let curve_is_valid = match upgrade.price_curve {
+                PriceCurve::Fixed => true,
+                PriceCurve::Linear(step) => step.is_finite() && step >= 0.,
+                PriceCurve::Exponential(factor) => factor.is_finite() && factor >= 1.,
+            };
+            if !curve_is_valid {
+                return invalid("gets cheaper the more it's bought");
+            }
+            if upgrade.max_level == Some(0) {
+                return invalid("can never be bought, its max level is 0");
+            }
+            for effect in &upgrade.effects {
+                if let UpgradeEffect::SplitTail(fraction) = effect {
+                    if !(0. ..=1.).contains(fraction) {
+                        return invalid(
+                            "has to keep between 0 and 1 of the tail when splitting it",
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads `.upgrades.ron` files, refusing any that name an icon that isn't there
+#[derive(Default)]
+pub struct UpgradeCatalogueLoader;
+
+impl AssetLoader for UpgradeCatalogueLoader {
+    type Asset = UpgradeCatalogue;
+    type Settings = ();
+    type Error = CatalogueError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<UpgradeCatalogue, CatalogueError>> {
+        Box::pin(async move {
+            let mut catalogue = Vec::new();
+            reader
+                .read_to_end(&mut catalogue)
+                .await
+                .map_err(CatalogueError::Io)?;
+            let catalogue = UpgradeCatalogue::parse(&catalogue)?;
+            for upgrade in &catalogue.upgrades {
+                let icon = format!("{ICON_FOLDER}/{}", upgrade.icon);
+                if load_context.read_asset_bytes(icon).await.is_err() {
+                    return Err(CatalogueError::Invalid(format!(
+                        "upgrade {} ({}) has an icon, {}, that isn't in assets/{ICON_FOLDER}",
+                        upgrade.id, upgrade.name, upgrade.icon
+                    )));
+                }
+            }
+            Ok(catalogue)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["upgrades.ron"]
+    }
+}
+
+/// The catalogue file, kept loaded so changes to it are picked up while the game runs
+#[derive(Resource)]
+pub struct UpgradeCatalogueHandle(pub Handle<UpgradeCatalogue>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(catalogue: &str) -> String {
+        match UpgradeCatalogue::parse(catalogue.as_bytes()) {
+            Err(CatalogueError::Invalid(reason)) => reason,
+            Err(e) => panic!("expected the catalogue to be invalid, it didn't parse: {e}"),
+            Ok(_) => panic!("expected the catalogue to be invalid"),
+        }
+    }
+
+    fn upgrade(id: usize, fields: &str) -> String {
+        format!(
+            r#"(id: {id}, name: "Test", description: "", icon: "test.png", price: 10.0, {fields})"#
+        )
+    }
+
+    #[test]
+    fn built_in_catalogue_is_valid() {
+        assert!(!UpgradeCatalogue::built_in().upgrades.is_empty());
+    }
+
+    #[test]
+    fn rejects_duplicate_ids() {
+        let catalogue = format!("(upgrades: [{}, {}])", upgrade(3, ""), upgrade(3, ""));
+        assert!(invalid(&catalogue).contains("same id"));
+    }
+
+    #[test]
+    fn rejects_price_curves_that_get_cheaper() {
+        for curve in ["Linear(-5.0)", "Exponential(0.5)"] {
+            let catalogue = format!(
+                "(upgrades: [{}])",
+                upgrade(0, &format!("price_curve: {curve}"))
+            );
+            assert!(invalid(&catalogue).contains("cheaper"), "{curve}");
+        }
+    }
+
+    #[test]
+    fn rejects_max_level_0() {
+        let catalogue = format!("(upgrades: [{}])", upgrade(0, "max_level: Some(0)"));
+        assert!(invalid(&catalogue).contains("max level is 0"));
+    }
+
+    #[test]
+    fn rejects_split_fractions_outside_0_to_1() {
+        for fraction in ["-0.5", "1.5"] {
+            let catalogue = format!(
+                "(upgrades: [{}])",
+                upgrade(0, &format!("effects: [SplitTail({fraction})]"))
+            );
+            assert!(
+                invalid(&catalogue).contains("between 0 and 1"),
+                "{fraction}"
+            );
+        }
+    }
+}
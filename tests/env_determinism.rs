@@ -0,0 +1,67 @@
+//! The same seed and the same actions have to play out the same way every time, or replays,
+//! online matches and anything trained on `SnakeEnv` stop making sense.
+
+use bevy::prelude::*;
+use snake::{
+    env::{Observation, SnakeEnv},
+    AppState, SnakeControls, SnakeGamePlugin,
+};
+
+const SEED: u64 = 42;
+const TICKS: u64 = 600;
+
+// turns steadily, so the snake goes round in wide circles, and back from the walls when it gets
+// close, so it lives long enough to eat and grow
+fn action(tick: u64, observation: &Observation) -> SnakeControls {
+    let angle = tick as f32 / 40.;
+    let away_from_walls = -observation.head_position / (observation.field_size / 2.);
+    SnakeControls {
+        direction: Vec2::new(angle.cos(), angle.sin()) + away_from_walls.powf(3.) * 2.,
+        ..default()
+    }
+}
+
+fn play(seed: u64) -> Vec<(Observation, f32, bool)> {
+    let mut env = SnakeEnv::new();
+    let mut steps = vec![(env.reset(seed), 0., false)];
+    for tick in 0..TICKS {
+        let step = env.step(action(tick, &steps.last().unwrap().0));
+        let done = step.2;
+        steps.push(step);
+        if done {
+            break;
+        }
+    }
+    steps
+}
+
+#[test]
+fn same_seed_plays_out_the_same() {
+    let first = play(SEED);
+    let second = play(SEED);
+    assert!(first.len() > 1);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn different_seeds_play_out_differently() {
+    assert_ne!(play(SEED), play(SEED + 1));
+}
+
+// with nothing handed to it, the plugin starts out with the built-in catalogues and no files
+#[test]
+fn game_plugin_runs_headless() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SnakeGamePlugin));
+    app.update();
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Playing);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(
+        *app.world.resource::<State<AppState>>().get(),
+        AppState::Playing
+    );
+}
@@ -0,0 +1,111 @@
+//! Two online games over 127.0.0.1 have to stay in lockstep, or the players end up seeing
+//! different matches.
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use snake::{
+    levels::Level,
+    net::{NetError, NetSession},
+    AppState, Game, RestartEvent, RoundResults, Snake, SnakeControls, SnakeGamePlugin,
+};
+
+// how many ticks have to be played, over as many rounds as it takes, for the match to count as
+// having stayed in step
+const TICKS: u64 = 1000;
+// gives up if the two sides never get that far
+const MAX_FRAMES: u64 = 60 * 60;
+
+fn headless_game(session: NetSession) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SnakeGamePlugin))
+        .insert_resource(session);
+    let timestep = app.world.resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+    app
+}
+
+// each side steers its own snake round in circles, the other way round from the other side, so
+// the inputs that have to be swapped differ every tick.  Both turn back from the walls
+fn steer(app: &mut App, frame: u64) {
+    let Some(local_player) = app
+        .world
+        .get_resource::<NetSession>()
+        .map(NetSession::local_player)
+    else {
+        return;
+    };
+    let turn = if local_player == 0 { 1. } else { -1. };
+    let angle = turn * frame as f32 / 30.;
+    let field_size = app.world.resource::<Level>().field_size;
+    let mut snakes = app
+        .world
+        .query::<(&Snake, &Transform, &mut SnakeControls)>();
+    for (snake, head, mut controls) in snakes.iter_mut(&mut app.world) {
+        if snake.player == local_player {
+            let away_from_walls = -head.translation.truncate() / (field_size / 2.);
+            controls.direction =
+                Vec2::new(angle.cos(), angle.sin()) + away_from_walls.powf(3.) * 2.;
+        }
+    }
+}
+
+fn state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+fn session(app: &App) -> &NetSession {
+    app.world
+        .get_resource::<NetSession>()
+        .expect("the connection was lost")
+}
+
+fn results(app: &App) -> Vec<(usize, usize, u64)> {
+    app.world
+        .resource::<RoundResults>()
+        .ranked()
+        .into_iter()
+        .map(|result| (result.player, result.score, result.ticks))
+        .collect()
+}
+
+#[test]
+fn two_sessions_stay_in_step() -> Result<(), NetError> {
+    let host = NetSession::host(0)?;
+    let port = host.local_addr().map_err(NetError::Io)?.port();
+    let client = NetSession::join(&format!("127.0.0.1:{port}"))?;
+    let mut host = headless_game(host);
+    let mut client = headless_game(client);
+
+    let mut played = 0;
+    let mut round = 1;
+    // whether both sides have got into the round yet, so the end of the last one isn't counted
+    let mut started = false;
+    for frame in 0..MAX_FRAMES {
+        steer(&mut host, frame);
+        steer(&mut client, frame);
+        host.update();
+        client.update();
+        assert!(!session(&host).desynced(), "the host desynced");
+        assert!(!session(&client).desynced(), "the client desynced");
+
+        let in_round = session(&host).round() == round && session(&client).round() == round;
+        let both_in = |app_state| state(&host) == app_state && state(&client) == app_state;
+        started |= in_round && both_in(AppState::Playing);
+        if !(started && both_in(AppState::GameOver)) {
+            continue;
+        }
+        assert_eq!(
+            results(&host),
+            results(&client),
+            "round {round} ended differently"
+        );
+        played += host.world.resource::<Game>().ticks;
+        if played >= TICKS {
+            return Ok(());
+        }
+        // only the host can start a round, the client follows
+        host.world.send_event(RestartEvent);
+        round += 1;
+        started = false;
+    }
+    panic!("only {played} ticks were played, in {round} rounds");
+}